@@ -0,0 +1,188 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use disjoint_set::{Compression, DenseDisjointSet, DisjointSet, FullCompression, NoCompression, PathHalving,
+                   PathSplitting};
+
+/// The `Rc<RefCell<SubSet<T>>>` implementation that `DisjointSet` used before the index arena, kept for comparison.
+mod rc_nodes {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::hash::Hash;
+    use std::rc::{Rc, Weak};
+
+    pub struct DisjointSet<T> {
+        elements: HashMap<T, Rc<RefCell<SubSet<T>>>>
+    }
+
+    struct SubSet<T> {
+        rank: u32,
+        value: T,
+        parent: Option<Weak<RefCell<SubSet<T>>>>
+    }
+
+    impl<T> DisjointSet<T>
+        where T: Eq + Hash + Clone
+    {
+        pub fn new() -> DisjointSet<T> {
+            DisjointSet { elements: HashMap::new() }
+        }
+
+        pub fn make_set(&mut self, value: T) {
+            let node = SubSet { rank: 0, value: value.clone(), parent: None };
+            self.elements.insert(value, Rc::new(RefCell::new(node)));
+        }
+
+        pub fn find(&mut self, value: T) -> Option<T> {
+            let mut root = self.elements.get(&value)?.clone();
+            let mut changed_nodes = Vec::new();
+            loop {
+                let parent = match root.borrow().parent {
+                    Some(ref p) => p.upgrade().unwrap(),
+                    None => break
+                };
+                changed_nodes.push(root);
+                root = parent;
+            }
+            for changed_node in changed_nodes.iter() {
+                changed_node.borrow_mut().parent = Some(Rc::downgrade(&root));
+            }
+            let result = root.borrow().value.clone();
+            Some(result)
+        }
+
+        pub fn union(&mut self, value_one: T, value_two: T) -> Option<T> {
+            let root_one = self.find(value_one)?;
+            let root_two = self.find(value_two)?;
+            if root_one == root_two {
+                return Some(root_one);
+            }
+            let one = self.elements[&root_one].clone();
+            let two = self.elements[&root_two].clone();
+            let (rank_one, rank_two) = (one.borrow().rank, two.borrow().rank);
+            if rank_one < rank_two {
+                one.borrow_mut().parent = Some(Rc::downgrade(&two));
+                Some(root_one)
+            } else {
+                two.borrow_mut().parent = Some(Rc::downgrade(&one));
+                if rank_one == rank_two {
+                    one.borrow_mut().rank += 1;
+                }
+                Some(root_two)
+            }
+        }
+    }
+}
+
+const SIZES: [u64; 2] = [10_000, 100_000];
+
+/// Deterministic pseudo-random pairs so both implementations see the same workload.
+fn pairs(n: u64) -> Vec<(u64, u64)> {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % n
+    };
+    (0..n).map(|_| (next(), next())).collect()
+}
+
+fn bench_union(c: &mut Criterion) {
+    let mut group = c.benchmark_group("union");
+    for &n in SIZES.iter() {
+        let edges = pairs(n);
+        group.bench_with_input(BenchmarkId::new("arena", n), &edges, |b, edges| {
+            b.iter(|| {
+                let mut set = DisjointSet::new();
+                for i in 0..n {
+                    set.make_set(i);
+                }
+                for &(x, y) in edges {
+                    black_box(set.union(x, y));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("rc_nodes", n), &edges, |b, edges| {
+            b.iter(|| {
+                let mut set = rc_nodes::DisjointSet::new();
+                for i in 0..n {
+                    set.make_set(i);
+                }
+                for &(x, y) in edges {
+                    black_box(set.union(x, y));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_find(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find");
+    for &n in SIZES.iter() {
+        let edges = pairs(n);
+
+        let mut arena = DisjointSet::new();
+        let mut rc = rc_nodes::DisjointSet::new();
+        for i in 0..n {
+            arena.make_set(i);
+            rc.make_set(i);
+        }
+        for &(x, y) in edges.iter() {
+            arena.union(x, y);
+            rc.union(x, y);
+        }
+
+        group.bench_function(BenchmarkId::new("arena", n), |b| {
+            b.iter(|| {
+                for i in 0..n {
+                    black_box(arena.find_mut(&i));
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("rc_nodes", n), |b| {
+            b.iter(|| {
+                for i in 0..n {
+                    black_box(rc.find(i));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Unions every pair and then finds every element, so both operations exercise the compression strategy.
+fn union_then_find<C: Compression>(n: u64, edges: &[(u64, u64)], compression: C) {
+    let mut set = DenseDisjointSet::with_compression(n as usize, compression);
+    for &(x, y) in edges {
+        black_box(set.union(x as usize, y as usize));
+    }
+    for i in 0..n as usize {
+        black_box(set.find(i));
+    }
+}
+
+fn bench_compression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compression");
+    for &n in SIZES.iter() {
+        let edges = pairs(n);
+        group.bench_with_input(BenchmarkId::new("full", n), &edges, |b, edges| {
+            b.iter(|| union_then_find(n, edges, FullCompression))
+        });
+        group.bench_with_input(BenchmarkId::new("halving", n), &edges, |b, edges| {
+            b.iter(|| union_then_find(n, edges, PathHalving))
+        });
+        group.bench_with_input(BenchmarkId::new("splitting", n), &edges, |b, edges| {
+            b.iter(|| union_then_find(n, edges, PathSplitting))
+        });
+        group.bench_with_input(BenchmarkId::new("none", n), &edges, |b, edges| {
+            b.iter(|| union_then_find(n, edges, NoCompression))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_union, bench_find, bench_compression);
+criterion_main!(benches);
@@ -0,0 +1,78 @@
+/// A strategy for shortening paths while finding the root of an element.
+///
+/// Every strategy keeps the amortized cost of `find` at O(α(n)) when combined with union by rank, except
+/// `NoCompression`, which leaves finds at O(log n) but never changes the forest.
+pub trait Compression {
+    /// Finds the root of `index` in the forest described by `parents`, where a root is its own parent.
+    fn find_root(&self, parents: &mut [usize], index: usize) -> usize;
+}
+
+/// Points every node on the path directly at the root, walking the path twice.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FullCompression;
+
+/// Points every other node on the path at its grandparent in a single pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PathHalving;
+
+/// Points every node on the path at its grandparent in a single pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PathSplitting;
+
+/// Leaves the forest unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoCompression;
+
+impl Compression for FullCompression {
+    fn find_root(&self, parents: &mut [usize], index: usize) -> usize {
+        // Finding the root
+        let mut root = index;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        
+        // Path compression on visited nodes
+        let mut node = index;
+        while node != root {
+            let parent = parents[node];
+            parents[node] = root;
+            node = parent;
+        }
+        
+        root
+    }
+}
+
+impl Compression for PathHalving {
+    fn find_root(&self, parents: &mut [usize], index: usize) -> usize {
+        let mut node = index;
+        while parents[node] != node {
+            let grandparent = parents[parents[node]];
+            parents[node] = grandparent;
+            node = grandparent;
+        }
+        node
+    }
+}
+
+impl Compression for PathSplitting {
+    fn find_root(&self, parents: &mut [usize], index: usize) -> usize {
+        let mut node = index;
+        while parents[node] != node {
+            let parent = parents[node];
+            parents[node] = parents[parent];
+            node = parent;
+        }
+        node
+    }
+}
+
+impl Compression for NoCompression {
+    fn find_root(&self, parents: &mut [usize], index: usize) -> usize {
+        let mut root = index;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        root
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::linking::priority;
+use crate::UnionResult;
+
+/// A disjoint-set over the integers `0..n` whose operations take `&self` and may be called from many threads at once.
+///
+/// Parents are stored in atomics and roots are linked with a compare-and-swap, following the randomized linking of
+/// Jayanti and Tarjan: every element has a fixed pseudo-random priority and a root is only ever linked under a root of
+/// higher priority, so the forest can never contain a cycle. `find` performs path halving with compare-and-swap.
+#[derive(Default)]
+pub struct ConcurrentDisjointSet {
+    parents: Vec<AtomicUsize>
+}
+
+impl ConcurrentDisjointSet {
+    /// Creates a `ConcurrentDisjointSet` containing the `n` singleton sets `{0}, {1}, ..., {n - 1}`.
+    pub fn new(n: usize) -> ConcurrentDisjointSet {
+        ConcurrentDisjointSet {
+            parents: (0..n).map(AtomicUsize::new).collect()
+        }
+    }
+    
+    /// Returns the number of elements in the `ConcurrentDisjointSet`.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+    
+    /// Returns `true` if the `ConcurrentDisjointSet` contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+    
+    /// Finds the root of the set that the element belongs to and performs path halving on the visited nodes.
+    ///
+    /// Returns `None` if the element is not in the `ConcurrentDisjointSet`. Under concurrent unions the returned root
+    /// may already have been linked under another root by the time the caller inspects it.
+    pub fn find(&self, element: usize) -> Option<usize> {
+        if element < self.parents.len() {
+            Some(self.find_root(element))
+        } else {
+            None
+        }
+    }
+    
+    /// Unions the two sets that each element belongs to.
+    ///
+    /// Returns `None` if one of the elements does not exist in the `ConcurrentDisjointSet`, otherwise reports the roots
+    /// involved at the moment the union took effect.
+    pub fn union(&self, element_one: usize, element_two: usize) -> Option<UnionResult<usize>> {
+        self.find(element_one)?;
+        self.find(element_two)?;
+        
+        loop {
+            let root_one = self.find_root(element_one);
+            let root_two = self.find_root(element_two);
+            
+            if root_one == root_two {
+                return Some(UnionResult::AlreadyJoined(root_one));
+            }
+            
+            let (root, child) = if priority(root_one as u64) < priority(root_two as u64) {
+                (root_two, root_one)
+            } else {
+                (root_one, root_two)
+            };
+            
+            // Only succeeds if `child` is still a root; otherwise another thread linked it first and we retry.
+            if self.parents[child].compare_exchange(child, root, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+                return Some(UnionResult::Merged { root, absorbed: child });
+            }
+        }
+    }
+    
+    /// Checks whether the two elements belong to the same set.
+    ///
+    /// Returns `None` if one of the elements does not exist in the `ConcurrentDisjointSet`.
+    pub fn same_set(&self, element_one: usize, element_two: usize) -> Option<bool> {
+        self.find(element_one)?;
+        self.find(element_two)?;
+        
+        loop {
+            let root_one = self.find_root(element_one);
+            let root_two = self.find_root(element_two);
+            
+            if root_one == root_two {
+                return Some(true);
+            }
+            
+            // If the first root is still a root, the sets were distinct when the second root was found.
+            if self.parents[root_one].load(Ordering::Acquire) == root_one {
+                return Some(false);
+            }
+        }
+    }
+    
+    /// Finds the root of `index`, pointing every other node on the path at its grandparent.
+    fn find_root(&self, index: usize) -> usize {
+        let mut node = index;
+        loop {
+            let parent = self.parents[node].load(Ordering::Acquire);
+            if parent == node {
+                return node;
+            }
+            let grandparent = self.parents[parent].load(Ordering::Acquire);
+            if grandparent != parent {
+                // Losing this race is harmless: the parent has only moved further up the same tree.
+                let _ = self.parents[node].compare_exchange_weak(parent, grandparent, Ordering::AcqRel, Ordering::Relaxed);
+            }
+            node = grandparent;
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use crate::{ByRank, Compression, Linking, PathHalving, RootInfo, UnionResult};
+
+/// A disjoint-set over the integers `0..n`, backed by plain vectors and requiring no hashing.
+///
+/// The members of each set are also threaded into a circular doubly linked list through `next` and `prev`, so a set
+/// can be enumerated without scanning every element.
+///
+/// Paths are shortened during `find` by the `Compression` strategy `C`, which defaults to path halving, and the root
+/// that survives a union is chosen by the `Linking` policy `L`, which defaults to union by rank.
+#[derive(Clone, Default)]
+pub struct DenseDisjointSet<C = PathHalving, L = ByRank> {
+    parents: Vec<usize>,
+    ranks: Vec<u32>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+    sizes: Vec<usize>,
+    num_sets: usize,
+    compression: C,
+    linking: L
+}
+
+impl DenseDisjointSet {
+    /// Creates a `DenseDisjointSet` containing the `n` singleton sets `{0}, {1}, ..., {n - 1}`.
+    pub fn new(n: usize) -> DenseDisjointSet {
+        DenseDisjointSet::with_strategies(n, PathHalving, ByRank)
+    }
+}
+
+impl<C> DenseDisjointSet<C>
+    where C: Compression
+{
+    /// Creates a `DenseDisjointSet` containing the `n` singleton sets `{0}, {1}, ..., {n - 1}` that shortens paths
+    /// with the given strategy.
+    pub fn with_compression(n: usize, compression: C) -> DenseDisjointSet<C> {
+        DenseDisjointSet::with_strategies(n, compression, ByRank)
+    }
+}
+
+impl<L> DenseDisjointSet<PathHalving, L>
+    where L: Linking<usize>
+{
+    /// Creates a `DenseDisjointSet` containing the `n` singleton sets `{0}, {1}, ..., {n - 1}` that links roots with
+    /// the given policy.
+    pub fn with_linking(n: usize, linking: L) -> DenseDisjointSet<PathHalving, L> {
+        DenseDisjointSet::with_strategies(n, PathHalving, linking)
+    }
+}
+
+impl<C, L> DenseDisjointSet<C, L>
+    where C: Compression, L: Linking<usize>
+{
+    /// Creates a `DenseDisjointSet` containing the `n` singleton sets `{0}, {1}, ..., {n - 1}` that shortens paths
+    /// and links roots with the given strategies.
+    pub fn with_strategies(n: usize, compression: C, linking: L) -> DenseDisjointSet<C, L> {
+        DenseDisjointSet {
+            parents: (0..n).collect(),
+            ranks: vec![0; n],
+            next: (0..n).collect(),
+            prev: (0..n).collect(),
+            sizes: vec![1; n],
+            num_sets: n,
+            compression,
+            linking
+        }
+    }
+    
+    /// Creates a `DenseDisjointSet` from a forest of parent pointers, given the root of every element as found by
+    /// `resolve_roots`.
+    pub(crate) fn from_parts(parents: Vec<usize>, ranks: Vec<u32>, roots: &[usize], compression: C, linking: L)
+        -> DenseDisjointSet<C, L>
+    {
+        let n = parents.len();
+        let mut dense = DenseDisjointSet {
+            parents,
+            ranks,
+            next: (0..n).collect(),
+            prev: (0..n).collect(),
+            sizes: vec![1; n],
+            num_sets: n,
+            compression,
+            linking
+        };
+        for (index, &root) in roots.iter().enumerate() {
+            if root != index {
+                dense.splice(root, index);
+                dense.sizes[root] += 1;
+                dense.num_sets -= 1;
+            }
+        }
+        dense
+    }
+    
+    /// Appends a new singleton set and returns its element, which is the previous number of elements.
+    pub fn make_set(&mut self) -> usize {
+        let index = self.parents.len();
+        self.parents.push(index);
+        self.ranks.push(0);
+        self.next.push(index);
+        self.prev.push(index);
+        self.sizes.push(1);
+        self.num_sets += 1;
+        index
+    }
+    
+    /// Returns the number of elements in the `DenseDisjointSet`.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+    
+    /// Returns `true` if the `DenseDisjointSet` contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+    
+    /// Returns the number of disjoint sets in the `DenseDisjointSet`.
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+    
+    /// Returns the number of elements in the set that the element belongs to.
+    ///
+    /// Returns `None` if the element is not in the `DenseDisjointSet`.
+    pub fn set_size(&mut self, element: usize) -> Option<usize> {
+        let root = self.find(element)?;
+        Some(self.sizes[root])
+    }
+    
+    /// Finds the root of the set that the element belongs to and shortens the path with the compression strategy.
+    ///
+    /// Returns `None` if the element is not in the `DenseDisjointSet`.
+    pub fn find(&mut self, element: usize) -> Option<usize> {
+        if element < self.parents.len() {
+            Some(self.find_root(element))
+        } else {
+            None
+        }
+    }
+    
+    /// Unions the two sets that each element belongs to, choosing the surviving root with the linking policy.
+    ///
+    /// Returns `None` if one of the elements does not exist in the `DenseDisjointSet`, otherwise reports the root of the
+    /// resulting set and whether a merge happened.
+    pub fn union(&mut self, element_one: usize, element_two: usize) -> Option<UnionResult<usize>> {
+        let root_one = self.find(element_one)?;
+        let root_two = self.find(element_two)?;
+        Some(self.union_roots(root_one, root_two))
+    }
+    
+    /// Returns an iterator over the elements of the set that the element belongs to, starting with the element itself.
+    ///
+    /// Returns `None` if the element is not in the `DenseDisjointSet`.
+    pub fn members(&self, element: usize) -> Option<impl Iterator<Item = usize> + '_> {
+        if element >= self.parents.len() {
+            return None;
+        }
+        let mut current = Some(element);
+        Some(std::iter::from_fn(move || {
+            let member = current?;
+            let next = self.next[member];
+            current = if next == element { None } else { Some(next) };
+            Some(member)
+        }))
+    }
+    
+    /// Returns an iterator over every set in the `DenseDisjointSet`, each listed starting with its root.
+    pub fn sets(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..self.parents.len())
+            .filter(move |&index| self.parents[index] == index && self.sizes[index] > 0)
+            .map(move |root| self.members(root).unwrap().collect())
+    }
+    
+    /// Checks whether the two elements belong to the same set, shortening both paths with the compression strategy.
+    ///
+    /// Returns `None` if one of the elements does not exist in the `DenseDisjointSet`.
+    pub fn same_set(&mut self, element_one: usize, element_two: usize) -> Option<bool> {
+        let root_one = self.find(element_one)?;
+        let root_two = self.find(element_two)?;
+        Some(root_one == root_two)
+    }
+    
+    /// Links two roots, choosing the one that survives with the linking policy.
+    pub(crate) fn union_roots(&mut self, root_one: usize, root_two: usize) -> UnionResult<usize> {
+        if root_one == root_two {
+            return UnionResult::AlreadyJoined(root_one);
+        }
+        
+        if self.linking.keeps_first(self.root_info(root_one, &root_one), self.root_info(root_two, &root_two)) {
+            self.link(root_one, root_two)
+        } else {
+            self.link(root_two, root_one)
+        }
+    }
+    
+    /// Describes a root for a linking policy, under the given key.
+    pub(crate) fn root_info<'a, K: ?Sized>(&self, root: usize, key: &'a K) -> RootInfo<'a, K> {
+        RootInfo {
+            index: root,
+            rank: self.ranks[root],
+            size: self.sizes[root],
+            key
+        }
+    }
+    
+    /// Attaches the root `child` under the root `root`, keeping the rank of `root` an upper bound on its height.
+    pub(crate) fn link(&mut self, root: usize, child: usize) -> UnionResult<usize> {
+        self.ranks[root] = self.ranks[root].max(self.ranks[child] + 1);
+        self.parents[child] = root;
+        self.splice(root, child);
+        self.sizes[root] += self.sizes[child];
+        self.num_sets -= 1;
+        UnionResult::Merged { root, absorbed: child }
+    }
+    
+    /// Reverses the most recent `union_roots` that attached `absorbed` under `root`, restoring `root`'s rank.
+    pub(crate) fn unlink(&mut self, root: usize, absorbed: usize, root_rank: u32) {
+        self.parents[absorbed] = absorbed;
+        self.ranks[root] = root_rank;
+        self.splice(root, absorbed);
+        self.sizes[root] -= self.sizes[absorbed];
+        self.num_sets += 1;
+    }
+    
+    /// Removes the most recently made element, which must still be a singleton.
+    pub(crate) fn pop_set(&mut self) {
+        self.parents.pop();
+        self.ranks.pop();
+        self.next.pop();
+        self.prev.pop();
+        self.sizes.pop();
+        self.num_sets -= 1;
+    }
+    
+    /// Removes an element from the member list and size of its set, leaving its node in the forest as a vacant
+    /// placeholder so that paths through it stay valid. A set whose last member is vacated no longer counts as a set.
+    ///
+    /// The element must not be the root of a set that still has other members.
+    pub(crate) fn vacate(&mut self, index: usize) {
+        let root = self.find_root(index);
+        self.sizes[root] -= 1;
+        if self.sizes[root] == 0 {
+            self.num_sets -= 1;
+        }
+        
+        let (prev, next) = (self.prev[index], self.next[index]);
+        self.next[prev] = next;
+        self.prev[next] = prev;
+        self.next[index] = index;
+        self.prev[index] = index;
+    }
+    
+    /// Removes every element, keeping the compression strategy.
+    pub(crate) fn clear(&mut self) {
+        self.parents.clear();
+        self.ranks.clear();
+        self.next.clear();
+        self.prev.clear();
+        self.sizes.clear();
+        self.num_sets = 0;
+    }
+    
+    /// Returns the member after `index` in the member list of its set.
+    pub(crate) fn next(&self, index: usize) -> usize {
+        self.next[index]
+    }
+    
+    /// Returns the parent of an element, which is the element itself for a root.
+    pub(crate) fn parent(&self, index: usize) -> usize {
+        self.parents[index]
+    }
+    
+    /// Returns the rank of a root.
+    pub(crate) fn rank(&self, root: usize) -> u32 {
+        self.ranks[root]
+    }
+    
+    /// Overwrites the rank of a root.
+    #[cfg(feature = "serde")]
+    pub(crate) fn set_rank(&mut self, root: usize, rank: u32) {
+        self.ranks[root] = rank;
+    }
+    
+    /// Returns the number of elements in the set of a root.
+    pub(crate) fn size(&self, root: usize) -> usize {
+        self.sizes[root]
+    }
+    
+    /// Joins the member lists of two different sets, or splits a list in two if both are in the same one, so that
+    /// calling it twice restores the original lists.
+    fn splice(&mut self, first: usize, second: usize) {
+        self.next.swap(first, second);
+        let (first_next, second_next) = (self.next[first], self.next[second]);
+        self.prev[first_next] = first;
+        self.prev[second_next] = second;
+    }
+    
+    /// Finds the root of `index` without modifying the forest.
+    pub(crate) fn root_of(&self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        root
+    }
+    
+    /// Finds the root of `index`, shortening the path with the compression strategy.
+    pub(crate) fn find_root(&mut self, index: usize) -> usize {
+        self.compression.find_root(&mut self.parents, index)
+    }
+}
+
+/// The state of a node while the roots of a forest are resolved.
+#[derive(Clone, Copy)]
+enum Visit {
+    Unvisited,
+    OnPath,
+    Resolved(usize)
+}
+
+/// Finds the root of every node in a forest of parent pointers, all of which must be in bounds.
+///
+/// Returns the position of a node whose parent pointers lead into a cycle if there is one.
+pub(crate) fn resolve_roots(parents: &[usize]) -> Result<Vec<usize>, usize> {
+    let mut visits = vec![Visit::Unvisited; parents.len()];
+    let mut path = Vec::new();
+    
+    for start in 0..parents.len() {
+        let mut index = start;
+        let root = loop {
+            match visits[index] {
+                Visit::Resolved(root) => break root,
+                Visit::OnPath => return Err(start),
+                Visit::Unvisited if parents[index] == index => break index,
+                Visit::Unvisited => {
+                    visits[index] = Visit::OnPath;
+                    path.push(index);
+                    index = parents[index];
+                }
+            }
+        };
+        visits[index] = Visit::Resolved(root);
+        for index in path.drain(..) {
+            visits[index] = Visit::Resolved(root);
+        }
+    }
+    
+    Ok(visits.into_iter().map(|visit| match visit {
+        Visit::Resolved(root) => root,
+        _ => unreachable!()
+    }).collect())
+}
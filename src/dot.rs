@@ -0,0 +1,80 @@
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+use crate::{Compression, DisjointSet, Linking};
+
+/// What `DisjointSet::to_dot` renders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DotStyle {
+    /// The forest as it is stored: every node with its rank and an edge to its parent. Roots are drawn with a double
+    /// outline and vacant nodes left behind by removal are drawn dashed.
+    Forest,
+    /// The partition alone: one cluster per set, listing its values, with the root drawn with a double outline.
+    Partition
+}
+
+impl<T, D, C, L> DisjointSet<T, D, C, L>
+    where T: Eq + Hash + Clone, C: Compression, L: Linking<T>
+{
+    /// Renders the `DisjointSet` in the Graphviz DOT language, labelling every value by its `Debug` representation.
+    pub fn to_dot(&self, style: DotStyle) -> String
+        where T: Debug
+    {
+        let mut dot = String::new();
+        match style {
+            DotStyle::Forest => {
+                dot.push_str("digraph disjoint_set {\n");
+                for (index, value) in self.values.iter().enumerate() {
+                    let rank = self.forest.rank(index);
+                    let parent = self.forest.parent(index);
+                    let label = match *value {
+                        Some(ref value) => format!("{:?}\\nrank {}", Escaped(value), rank),
+                        None => format!("(vacant)\\nrank {}", rank)
+                    };
+                    let outline = match (value.is_some(), parent == index) {
+                        (false, _) => ", style=dashed",
+                        (true, true) => ", peripheries=2",
+                        (true, false) => ""
+                    };
+                    writeln!(dot, "    n{} [label=\"{}\"{}];", index, label, outline).unwrap();
+                }
+                for index in 0..self.values.len() {
+                    let parent = self.forest.parent(index);
+                    if parent != index {
+                        writeln!(dot, "    n{} -> n{};", index, parent).unwrap();
+                    }
+                }
+            }
+            DotStyle::Partition => {
+                dot.push_str("graph disjoint_set {\n");
+                for (number, set) in self.forest.sets().enumerate() {
+                    writeln!(dot, "    subgraph cluster_{} {{", number).unwrap();
+                    writeln!(dot, "        label=\"size {}\";", set.len()).unwrap();
+                    for (position, &index) in set.iter().enumerate() {
+                        let outline = if position == 0 { ", peripheries=2" } else { "" };
+                        writeln!(dot, "        n{} [label=\"{:?}\"{}];", index, Escaped(self.value(index)), outline).unwrap();
+                    }
+                    dot.push_str("    }\n");
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Formats a value through `Debug`, escaping it for use inside a quoted DOT string.
+struct Escaped<'a, T: ?Sized>(&'a T);
+
+impl<'a, T: Debug + ?Sized> Debug for Escaped<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let debug = format!("{:?}", self.0);
+        for c in debug.chars() {
+            if c == '"' || c == '\\' {
+                f.write_char('\\')?;
+            }
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
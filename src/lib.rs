@@ -1,117 +1,572 @@
-#![feature(alloc)]
-
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
 use std::hash::Hash;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+mod compression;
+mod concurrent;
+mod dense;
+mod dot;
+mod linking;
+mod parity;
+mod persistent;
+mod persistent_map;
+mod rollback;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod snapshot;
+mod timed;
+mod weighted;
+
+pub use compression::{Compression, FullCompression, NoCompression, PathHalving, PathSplitting};
+pub use concurrent::ConcurrentDisjointSet;
+pub use dense::DenseDisjointSet;
+pub use dot::DotStyle;
+pub use linking::{ByMinKey, ByRandomPriority, ByRank, BySize, Linking, RootInfo};
+pub use parity::{Parity, ParityDisjointSet};
+pub use persistent::PersistentDisjointSet;
+pub use rollback::{Checkpoint, RollbackDisjointSet};
+pub use snapshot::{DenseSnapshot, SnapshotValue};
+pub use timed::TimedDisjointSet;
+pub use weighted::{Group, WeightedDisjointSet, WeightedUnionError};
+
+/// The outcome of a union between two values that both exist in the set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnionResult<T> {
+    /// The two sets were merged: `absorbed` was attached under `root`, which now represents the whole set.
+    Merged { root: T, absorbed: T },
+    /// The values were already in the same set, represented by the contained root.
+    AlreadyJoined(T)
+}
+
+impl<T> UnionResult<T> {
+    /// Returns the root that represents the set after the union.
+    pub fn root(&self) -> &T {
+        match *self {
+            UnionResult::Merged { ref root, .. } => root,
+            UnionResult::AlreadyJoined(ref root) => root
+        }
+    }
+    
+    /// Returns `true` if the union merged two distinct sets.
+    pub fn merged(&self) -> bool {
+        matches!(*self, UnionResult::Merged { .. })
+    }
+    
+    /// Maps the roots contained in the result with the given function.
+    pub fn map<U, F>(self, mut f: F) -> UnionResult<U>
+        where F: FnMut(T) -> U
+    {
+        match self {
+            UnionResult::Merged { root, absorbed } => UnionResult::Merged { root: f(root), absorbed: f(absorbed) },
+            UnionResult::AlreadyJoined(root) => UnionResult::AlreadyJoined(f(root))
+        }
+    }
+}
+
+/// The outcome of `DisjointSet::make_set_or_get`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MakeSetResult<T> {
+    /// The value was new and now forms a singleton set.
+    Created,
+    /// The value was already present and was left untouched; contains the root of its set.
+    Existing(T)
+}
+
+/// The reason a `DisjointSet::try_union` was rejected, naming the arguments that are not in the `DisjointSet`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnionError {
+    /// The first value is not in the `DisjointSet`.
+    MissingFirst,
+    /// The second value is not in the `DisjointSet`.
+    MissingSecond,
+    /// Neither value is in the `DisjointSet`.
+    MissingBoth
+}
+
+impl fmt::Display for UnionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UnionError::MissingFirst => write!(f, "first value is not in the set"),
+            UnionError::MissingSecond => write!(f, "second value is not in the set"),
+            UnionError::MissingBoth => write!(f, "neither value is in the set")
+        }
+    }
+}
+
+impl Error for UnionError {}
+
+/// Data attached to each set of a `DisjointSet`, combined whenever two sets are merged.
+pub trait Merge {
+    /// Folds the data of a set that is being absorbed into the data of the surviving set.
+    fn merge(&mut self, absorbed: Self);
+}
 
-use std::cell::RefCell;
-use std::rc::Rc;
-use std::rc::Weak;
+impl Merge for () {
+    fn merge(&mut self, _: ()) {}
+}
 
 /// Struct that represents the [Disjoint-Set](http://en.wikipedia.org/wiki/Disjoint-set_data_structure) data structure.
+///
+/// Each value is mapped to an index into a `DenseDisjointSet`, which holds the forest itself. Since the forest is
+/// stored by value, cloning a `DisjointSet` produces an independent copy of the partition.
+///
+/// Every set can also carry data of type `D`, held by its root and combined through `Merge` when sets are unioned.
+/// Sets with data are created through `DisjointSet::default()`.
+///
+/// Paths are shortened by the `Compression` strategy `C` whenever the `DisjointSet` is searched through `&mut self`,
+/// which defaults to path halving. The root that survives a union is chosen by the `Linking` policy `L`, which defaults
+/// to union by rank; `ByMinKey` makes the minimum of every set its representative regardless of the order of unions.
+///
+/// Removed values leave a vacant node behind in the forest, so removal never has to restructure a tree. Once vacant
+/// nodes outnumber values the forest is rebuilt from scratch, which keeps removal amortized O(α(n)), except that
+/// removing the root of a set under a policy that chooses by key, such as `ByMinKey`, costs O(set size).
+///
+/// With the `serde` feature enabled, a `DisjointSet` serializes as a list of nodes, each naming its value, the root
+/// of its set and its rank, followed by the data of every set. Deserialization accepts any forest of parent pointers
+/// and rejects duplicate values, parents that are not in the set, cycles and sets with missing data.
 #[derive(Clone)]
-pub struct DisjointSet<T> {
-    elements: HashMap<T, Rc<RefCell<SubSet<T>>>>
+pub struct DisjointSet<T, D = (), C = PathHalving, L = ByRank> {
+    indices: HashMap<T, usize>,
+    values: Vec<Option<T>>,
+    data: Vec<Option<D>>,
+    forest: DenseDisjointSet<C>,
+    linking: L,
+    vacant: usize
+}
+
+impl<T, D, C, L> Default for DisjointSet<T, D, C, L>
+    where T: Eq + Hash + Clone, C: Compression + Default, L: Linking<T> + Default
+{
+    fn default() -> DisjointSet<T, D, C, L> {
+        DisjointSet::with_strategies(C::default(), L::default())
+    }
+}
+
+impl<T, D, C, L> FromIterator<T> for DisjointSet<T, D, C, L>
+    where T: Eq + Hash + Clone, D: Default, C: Compression + Default, L: Linking<T> + Default
+{
+    /// Makes a singleton set of every value, skipping repeated values.
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> DisjointSet<T, D, C, L> {
+        let mut disjoint_set = DisjointSet::default();
+        disjoint_set.extend(values);
+        disjoint_set
+    }
+}
+
+impl<T, D, C, L> Extend<T> for DisjointSet<T, D, C, L>
+    where T: Eq + Hash + Clone, D: Default, C: Compression, L: Linking<T>
+{
+    /// Makes a singleton set of every value that is not already in the `DisjointSet`.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.make_set(value);
+        }
+    }
 }
 
 impl<T> DisjointSet<T>
-    where T: Eq + PartialEq + Hash + Clone
+    where T: Eq + Hash + Clone
 {
     pub fn new() -> DisjointSet<T> {
+        DisjointSet::default()
+    }
+    
+    /// Creates a `DisjointSet` of every value that appears in the edges, with the two values of each edge unioned.
+    pub fn from_edges<I>(edges: I) -> DisjointSet<T>
+        where I: IntoIterator<Item = (T, T)>
+    {
+        let mut disjoint_set = DisjointSet::new();
+        for (value_one, value_two) in edges {
+            disjoint_set.union_insert(value_one, value_two);
+        }
+        disjoint_set
+    }
+}
+
+impl<T, C> DisjointSet<T, (), C>
+    where T: Eq + Hash + Clone, C: Compression
+{
+    /// Creates an empty `DisjointSet` that shortens paths with the given strategy.
+    pub fn with_compression(compression: C) -> DisjointSet<T, (), C> {
+        DisjointSet::with_strategies(compression, ByRank)
+    }
+}
+
+impl<T, L> DisjointSet<T, (), PathHalving, L>
+    where T: Eq + Hash + Clone, L: Linking<T>
+{
+    /// Creates an empty `DisjointSet` that links roots with the given policy.
+    pub fn with_linking(linking: L) -> DisjointSet<T, (), PathHalving, L> {
+        DisjointSet::with_strategies(PathHalving, linking)
+    }
+}
+
+impl<T, D, C, L> DisjointSet<T, D, C, L>
+    where T: Eq + Hash + Clone, C: Compression, L: Linking<T>
+{
+    /// Creates an empty `DisjointSet` that shortens paths and links roots with the given strategies.
+    pub fn with_strategies(compression: C, linking: L) -> DisjointSet<T, D, C, L> {
         DisjointSet {
-            elements: HashMap::new()
+            indices: HashMap::new(),
+            values: Vec::new(),
+            data: Vec::new(),
+            forest: DenseDisjointSet::with_compression(0, compression),
+            linking,
+            vacant: 0
+        }
+    }
+    
+    /// Makes a singleton set of the value inside the `DisjointSet` with default data.
+    ///
+    /// Returns `false` and leaves the existing set untouched if the value is already in the `DisjointSet`.
+    pub fn make_set(&mut self, value: T) -> bool
+        where D: Default
+    {
+        self.make_set_with(value, D::default())
+    }
+    
+    /// Makes a singleton set of the value inside the `DisjointSet` carrying the given data.
+    ///
+    /// Returns `false` and leaves the existing set and its data untouched if the value is already in the `DisjointSet`.
+    pub fn make_set_with(&mut self, value: T, data: D) -> bool {
+        if self.indices.contains_key(&value) {
+            return false;
         }
+        let index = self.forest.make_set();
+        self.indices.insert(value.clone(), index);
+        self.values.push(Some(value));
+        self.data.push(Some(data));
+        true
     }
     
-    /// Makes a singleton set of the value inside the `DisjointSet`.
-    pub fn make_set(&mut self, value: T) -> () {
-        self.elements.insert(value.clone(), Rc::new(RefCell::new(SubSet::new(value))));
+    /// Makes a singleton set of the value if it is not already in the `DisjointSet`, otherwise finds the root of the
+    /// set it belongs to.
+    pub fn make_set_or_get(&mut self, value: T) -> MakeSetResult<T>
+        where D: Default
+    {
+        match self.find_mut(&value) {
+            Some(root) => MakeSetResult::Existing(root.clone()),
+            None => {
+                self.make_set(value);
+                MakeSetResult::Created
+            }
+        }
     }
     
-    /// Finds the value of the root of the set that the value belongs to and performs path compression on the visited nodes.
+    /// Removes the value from the `DisjointSet`, leaving the rest of its set together. If the value was the last one in
+    /// its set, the set and its data are dropped.
     ///
     /// Returns `None` if the value is not in the `DisjointSet`.
-    pub fn find(&mut self, value: T) -> Option<T> {
-        let mut root;
-        let mut changed_nodes = Vec::new();
+    pub fn remove<Q>(&mut self, value: &Q) -> Option<T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = self.indices.remove(value)?;
+        let root = self.forest.find_root(index);
         
-        // Finding the root
-        match self.elements.get(&value) {
-            None => return None,
-            Some(n) => {
-                root = n.clone();
-                while root.borrow().parent.is_some() {
-                    changed_nodes.push(root.clone());
-                    let root_clone = root.clone();
-                    root = root_clone.borrow().parent.as_ref().unwrap().clone().upgrade().unwrap();
-                    // root = root.clone().borrow().parent.as_ref().unwrap().clone().upgrade().unwrap();
-                }
+        let removed = if root == index && self.forest.size(root) > 1 {
+            // The root has to stay occupied, so the value of the member that the linking policy would keep as root moves
+            // into it and that member is vacated.
+            let successor = self.successor(root);
+            let moved = self.values[successor].take().unwrap();
+            *self.indices.get_mut::<T>(&moved).unwrap() = index;
+            self.forest.vacate(successor);
+            self.values[index].replace(moved)
+        } else {
+            self.forest.vacate(index);
+            if root == index {
+                self.data[root] = None;
             }
-        }
+            self.values[index].take()
+        };
         
-        // Path compression on visited nodes
-        for changed_node in changed_nodes.iter() {
-            changed_node.borrow_mut().parent = Some(root.clone().downgrade());
+        self.vacant += 1;
+        if self.vacant > self.indices.len() {
+            self.rebuild();
         }
-        
-        let result = root.borrow().value.clone();
-        Some(result)
+        removed
     }
     
-    /// Unions the two sets that each value belongs to using union by rank.
+    /// Moves the value out of its set into a new singleton set with default data, leaving the rest of its set together.
+    /// A value that is already a singleton stays in place and has its data reset to the default.
     ///
-    /// Returns `None` if one of the values does not exist in the `DisjointSet`.
-    pub fn union(&mut self, value_one: T, value_two: T) -> Option<T> {
-        let root_one;
-        match self.find(value_one) {
-            Some(r) => root_one = r,
-            None => return None
+    /// Returns `false` if the value is not in the `DisjointSet`.
+    pub fn isolate<Q>(&mut self, value: &Q) -> bool
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized, D: Default
+    {
+        match self.set_size(value) {
+            None => false,
+            Some(1) => {
+                *self.set_data_mut(value).unwrap() = D::default();
+                true
+            }
+            Some(_) => {
+                let value = self.remove(value).unwrap();
+                self.make_set(value)
+            }
         }
-        
-        let root_two;
-        match self.find(value_two) {
-            Some(r) => root_two = r,
-            None => return None
+    }
+    
+    /// Returns the number of values in the `DisjointSet`.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+    
+    /// Returns `true` if the `DisjointSet` contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+    
+    /// Returns the number of disjoint sets in the `DisjointSet`.
+    pub fn num_sets(&self) -> usize {
+        self.forest.num_sets()
+    }
+    
+    /// Returns the number of values in the set that the value belongs to.
+    ///
+    /// Returns `None` if the value is not in the `DisjointSet`.
+    pub fn set_size<Q>(&self, value: &Q) -> Option<usize>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        Some(self.forest.size(self.forest.root_of(index)))
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to without modifying the `DisjointSet`.
+    ///
+    /// Returns `None` if the value is not in the `DisjointSet`.
+    pub fn find<Q>(&self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        let root = self.forest.root_of(index);
+        Some(self.value(root))
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to and shortens the path with the compression strategy.
+    ///
+    /// Returns `None` if the value is not in the `DisjointSet`.
+    pub fn find_mut<Q>(&mut self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        let root = self.forest.find_root(index);
+        Some(self.value(root))
+    }
+    
+    /// Unions the two sets that each value belongs to, choosing the surviving root with the linking policy and merging
+    /// the data of the absorbed set into the data of the surviving one.
+    ///
+    /// Returns `None` if one of the values does not exist in the `DisjointSet`, otherwise reports the root of the
+    /// resulting set and whether a merge happened.
+    pub fn union(&mut self, value_one: T, value_two: T) -> Option<UnionResult<T>>
+        where D: Merge
+    {
+        self.union_with(value_one, value_two, D::merge)
+    }
+    
+    /// Unions the two sets that each value belongs to, choosing the surviving root with the linking policy and calling
+    /// `merge` with the data of the surviving set and the data of the absorbed set if a merge happens.
+    ///
+    /// Returns `None` if one of the values does not exist in the `DisjointSet`, otherwise reports the root of the
+    /// resulting set and whether a merge happened.
+    pub fn union_with<F>(&mut self, value_one: T, value_two: T, merge: F) -> Option<UnionResult<T>>
+        where F: FnOnce(&mut D, D)
+    {
+        let index_one = *self.indices.get(&value_one)?;
+        let index_two = *self.indices.get(&value_two)?;
+        Some(self.union_indices(index_one, index_two, merge))
+    }
+    
+    /// Unions the two sets that each value belongs to like `union`, first making a singleton set with default data of
+    /// each value that is not already in the `DisjointSet`.
+    pub fn union_insert(&mut self, value_one: T, value_two: T) -> UnionResult<T>
+        where D: Default + Merge
+    {
+        let index_one = self.index_or_insert(value_one);
+        let index_two = self.index_or_insert(value_two);
+        self.union_indices(index_one, index_two, D::merge)
+    }
+    
+    /// Unions the two sets that each value belongs to like `union`.
+    ///
+    /// Returns an error naming the values that are not in the `DisjointSet`, which is left untouched.
+    pub fn try_union(&mut self, value_one: T, value_two: T) -> Result<UnionResult<T>, UnionError>
+        where D: Merge
+    {
+        match (self.indices.get(&value_one), self.indices.get(&value_two)) {
+            (Some(&index_one), Some(&index_two)) => Ok(self.union_indices(index_one, index_two, D::merge)),
+            (None, Some(_)) => Err(UnionError::MissingFirst),
+            (Some(_), None) => Err(UnionError::MissingSecond),
+            (None, None) => Err(UnionError::MissingBoth)
         }
+    }
+    
+    /// Unions the sets of two occupied indices, merging their data with `merge` if a merge happens.
+    fn union_indices<F>(&mut self, index_one: usize, index_two: usize, merge: F) -> UnionResult<T>
+        where F: FnOnce(&mut D, D)
+    {
+        let root_one = self.forest.find_root(index_one);
+        let root_two = self.forest.find_root(index_two);
         
-        let root_one_pointer = self.elements.get(&root_one).unwrap().clone();
-        let root_two_pointer = self.elements.get(&root_two).unwrap().clone();
+        let result = if root_one == root_two {
+            UnionResult::AlreadyJoined(root_one)
+        } else {
+            let first = self.forest.root_info(root_one, self.value(root_one));
+            let second = self.forest.root_info(root_two, self.value(root_two));
+            if self.linking.keeps_first(first, second) {
+                self.forest.link(root_one, root_two)
+            } else {
+                self.forest.link(root_two, root_one)
+            }
+        };
         
-        let root_one_rank = root_one_pointer.borrow().rank;
-        let root_two_rank = root_two_pointer.borrow().rank;
+        if let UnionResult::Merged { root, absorbed } = result {
+            let absorbed_data = self.data[absorbed].take().unwrap();
+            merge(self.data[root].as_mut().unwrap(), absorbed_data);
+        }
+        result.map(|index| self.value(index).clone())
+    }
+    
+    /// Returns the data of the set that the value belongs to.
+    ///
+    /// Returns `None` if the value is not in the `DisjointSet`.
+    pub fn set_data<Q>(&self, value: &Q) -> Option<&D>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        self.data[self.forest.root_of(index)].as_ref()
+    }
+    
+    /// Returns a mutable reference to the data of the set that the value belongs to, shortening the path
+    /// with the compression strategy.
+    ///
+    /// Returns `None` if the value is not in the `DisjointSet`.
+    pub fn set_data_mut<Q>(&mut self, value: &Q) -> Option<&mut D>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        let root = self.forest.find_root(index);
+        self.data[root].as_mut()
+    }
+    
+    /// Returns an iterator over the values of the set that the value belongs to, starting with the value itself.
+    ///
+    /// Returns `None` if the value is not in the `DisjointSet`.
+    pub fn members<Q>(&self, value: &Q) -> Option<impl Iterator<Item = &T> + '_>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        let members = self.forest.members(index)?;
+        Some(members.map(move |member| self.value(member)))
+    }
+    
+    /// Returns an iterator over every set in the `DisjointSet`, each listed starting with its root.
+    pub fn sets(&self) -> impl Iterator<Item = Vec<&T>> + '_ {
+        self.forest.sets().map(move |set| set.into_iter().map(|member| self.value(member)).collect())
+    }
+    
+    /// Checks whether the two values belong to the same set without modifying the `DisjointSet`.
+    ///
+    /// Returns `None` if one of the values does not exist in the `DisjointSet`.
+    pub fn same_set<Q>(&self, value_one: &Q, value_two: &Q) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index_one = *self.indices.get(value_one)?;
+        let index_two = *self.indices.get(value_two)?;
+        Some(self.forest.root_of(index_one) == self.forest.root_of(index_two))
+    }
+    
+    /// Checks whether the two values belong to the same set, shortening both paths with the compression strategy.
+    ///
+    /// Returns `None` if one of the values does not exist in the `DisjointSet`.
+    pub fn same_set_mut<Q>(&mut self, value_one: &Q, value_two: &Q) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index_one = *self.indices.get(value_one)?;
+        let index_two = *self.indices.get(value_two)?;
+        self.forest.same_set(index_one, index_two)
+    }
+    
+    /// Returns the index of the value, first making a singleton set with default data of it if it is not in the
+    /// `DisjointSet`. Takes a single hash lookup either way.
+    fn index_or_insert(&mut self, value: T) -> usize
+        where D: Default
+    {
+        match self.indices.entry(value) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let index = self.forest.make_set();
+                self.values.push(Some(entry.key().clone()));
+                self.data.push(Some(D::default()));
+                entry.insert(index);
+                index
+            }
+        }
+    }
+    
+    /// Chooses the member that replaces a root being removed from a set with other members. A policy that chooses by
+    /// key compares the root's tree under the value of every other member in turn; for any other policy the members
+    /// are interchangeable and the next one is taken.
+    fn successor(&self, root: usize) -> usize {
+        let mut successor = self.forest.next(root);
+        if !self.linking.chooses_by_key() {
+            return successor;
+        }
+        let mut member = self.forest.next(successor);
+        while member != root {
+            let first = self.forest.root_info(root, self.value(successor));
+            let second = self.forest.root_info(root, self.value(member));
+            if !self.linking.keeps_first(first, second) {
+                successor = member;
+            }
+            member = self.forest.next(member);
+        }
+        successor
+    }
+    
+    /// Returns the rank to record for an occupied node when the forest is written out flattened and without its vacant
+    /// nodes: zero for every non-root, and the rank of a root capped below the number of values, which it can reach
+    /// while vacant nodes still count towards the height of its tree.
+    fn flattened_rank(&self, index: usize) -> u32 {
+        if self.forest.parent(index) != index {
+            return 0;
+        }
+        let cap = u32::try_from(self.len() - 1).unwrap_or(u32::MAX);
+        self.forest.rank(index).min(cap)
+    }
+    
+    /// Returns the value stored at an occupied index.
+    fn value(&self, index: usize) -> &T {
+        self.values[index].as_ref().unwrap()
+    }
+    
+    /// Rebuilds the forest without its vacant nodes, keeping the representative and data of every set.
+    fn rebuild(&mut self) {
+        let sets: Vec<Vec<usize>> = self.forest.sets().collect();
+        self.forest.clear();
+        let mut values = Vec::with_capacity(self.indices.len());
+        let mut data = Vec::with_capacity(self.indices.len());
         
-        if root_one == root_two {
-            return Some(root_one);
+        for set in sets {
+            let root = self.forest.make_set();
+            values.push(self.values[set[0]].take());
+            data.push(self.data[set[0]].take());
+            for &member in &set[1..] {
+                let index = self.forest.make_set();
+                self.forest.link(root, index);
+                values.push(self.values[member].take());
+                data.push(None);
+            }
         }
         
-        if root_one_rank < root_two_rank {
-            root_one_pointer.borrow_mut().parent = Some(root_two_pointer.clone().downgrade());
-            return Some(root_one);
-        } else if root_one_rank > root_two_rank {
-            root_two_pointer.borrow_mut().parent = Some(root_one_pointer.clone().downgrade());
-            return Some(root_two);
-        } else {
-            root_two_pointer.borrow_mut().parent = Some(root_one_pointer.clone().downgrade());
-            root_one_pointer.borrow_mut().rank = root_one_rank + 1;
-            return Some(root_two);
+        for (index, value) in values.iter().enumerate() {
+            *self.indices.get_mut(value.as_ref().unwrap()).unwrap() = index;
         }
+        self.values = values;
+        self.data = data;
+        self.vacant = 0;
     }
 }
-
-
-
-#[derive(Clone)]
-struct SubSet<T> {
-    rank: u32,
-    value: T,
-    parent: Option<Weak<RefCell<SubSet<T>>>>
-}
-
-impl<T> SubSet<T> {
-    fn new(value: T) -> SubSet<T> {
-        SubSet {
-            rank: 0,
-            value: value,
-            parent: None
-        }
-    }
-}
\ No newline at end of file
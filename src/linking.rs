@@ -0,0 +1,93 @@
+/// A root that is about to be linked with another, as seen by a `Linking` policy.
+#[derive(Debug)]
+pub struct RootInfo<'a, K: ?Sized> {
+    /// The index of the root's node.
+    pub index: usize,
+    /// An upper bound on the height of the root's tree.
+    pub rank: u32,
+    /// The number of elements in the root's set.
+    pub size: usize,
+    /// The root's key: its value in a `DisjointSet`, or its element in a `DenseDisjointSet`.
+    pub key: &'a K
+}
+
+/// A policy that decides which of two roots survives when their sets are unioned.
+///
+/// The rank of the surviving root is kept as an upper bound on the height of its tree whatever the policy.
+pub trait Linking<K: ?Sized> {
+    /// Returns `true` if `first` should stay a root and `second` be attached under it.
+    fn keeps_first(&self, first: RootInfo<K>, second: RootInfo<K>) -> bool;
+    
+    /// Returns `true` if the policy compares keys, so that a `DisjointSet` removing the root of a set has to pick the
+    /// member that replaces it with `keeps_first`, in O(set size). Otherwise any member will do and removal stays O(1).
+    fn chooses_by_key(&self) -> bool {
+        false
+    }
+}
+
+/// Union by rank: the root of lower rank is attached under the other, and on ties the second root goes under the first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ByRank;
+
+/// Union by size: the root of the smaller set is attached under the other, and on ties the second root goes under the
+/// first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BySize;
+
+/// Randomized linking: every node has a fixed pseudo-random priority derived from its index and the seed, and the root
+/// of lower priority is attached under the other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ByRandomPriority {
+    seed: u64
+}
+
+/// The root with the smaller key survives, so every set is represented by its minimum, independently of the order of
+/// unions.
+///
+/// In a `DisjointSet`, removing the minimum of a set costs O(set size), since the new minimum has to be found among the
+/// remaining members.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ByMinKey;
+
+impl ByRandomPriority {
+    /// Creates a policy whose priorities are derived from the given seed.
+    pub fn new(seed: u64) -> ByRandomPriority {
+        ByRandomPriority { seed }
+    }
+}
+
+impl<K: ?Sized> Linking<K> for ByRank {
+    fn keeps_first(&self, first: RootInfo<K>, second: RootInfo<K>) -> bool {
+        first.rank >= second.rank
+    }
+}
+
+impl<K: ?Sized> Linking<K> for BySize {
+    fn keeps_first(&self, first: RootInfo<K>, second: RootInfo<K>) -> bool {
+        first.size >= second.size
+    }
+}
+
+impl<K: ?Sized> Linking<K> for ByRandomPriority {
+    fn keeps_first(&self, first: RootInfo<K>, second: RootInfo<K>) -> bool {
+        priority(first.index as u64 ^ self.seed) >= priority(second.index as u64 ^ self.seed)
+    }
+}
+
+impl<K: Ord + ?Sized> Linking<K> for ByMinKey {
+    fn keeps_first(&self, first: RootInfo<K>, second: RootInfo<K>) -> bool {
+        first.key <= second.key
+    }
+    
+    fn chooses_by_key(&self) -> bool {
+        true
+    }
+}
+
+/// A fixed pseudo-random permutation of the 64-bit integers (the SplitMix64 finalizer), so distinct inputs never tie.
+pub(crate) fn priority(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,130 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use crate::{Group, UnionResult, WeightedDisjointSet, WeightedUnionError};
+
+/// Whether two values are constrained to be on the same side or on opposite sides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Parity {
+    Same,
+    Different
+}
+
+/// Parities form the group of order two, where combining two `Different` relations gives `Same`.
+impl Group for Parity {
+    fn identity() -> Parity {
+        Parity::Same
+    }
+    
+    fn combine(&self, other: &Parity) -> Parity {
+        if self == other { Parity::Same } else { Parity::Different }
+    }
+    
+    fn inverse(&self) -> Parity {
+        *self
+    }
+}
+
+/// A disjoint-set that records whether values in the same set are on the same side or on opposite sides, for
+/// bipartiteness checks and two-colouring constraints.
+///
+/// This is a `WeightedDisjointSet` whose offsets are `Parity` values, so a union that closes an odd cycle is reported
+/// as `WeightedUnionError::Inconsistent` at the moment it is attempted.
+#[derive(Clone)]
+pub struct ParityDisjointSet<T> {
+    inner: WeightedDisjointSet<T, Parity>
+}
+
+impl<T> Default for ParityDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    fn default() -> ParityDisjointSet<T> {
+        ParityDisjointSet::new()
+    }
+}
+
+impl<T> ParityDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    pub fn new() -> ParityDisjointSet<T> {
+        ParityDisjointSet {
+            inner: WeightedDisjointSet::new()
+        }
+    }
+    
+    /// Makes a singleton set of the value inside the `ParityDisjointSet`.
+    ///
+    /// Returns `false` and leaves the existing set untouched if the value is already in the `ParityDisjointSet`.
+    pub fn make_set(&mut self, value: T) -> bool {
+        self.inner.make_set(value)
+    }
+    
+    /// Returns the number of values in the `ParityDisjointSet`.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+    
+    /// Returns `true` if the `ParityDisjointSet` contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+    
+    /// Returns the number of disjoint sets in the `ParityDisjointSet`.
+    pub fn num_sets(&self) -> usize {
+        self.inner.num_sets()
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to without modifying the `ParityDisjointSet`.
+    ///
+    /// Returns `None` if the value is not in the `ParityDisjointSet`.
+    pub fn find<Q>(&self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.inner.find(value)
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to and performs path compression on the visited nodes.
+    ///
+    /// Returns `None` if the value is not in the `ParityDisjointSet`.
+    pub fn find_mut<Q>(&mut self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.inner.find_mut(value)
+    }
+    
+    /// Unions the two sets that each value belongs to, recording the given relation between the two values.
+    ///
+    /// Fails with `WeightedUnionError::Inconsistent` if the values are already in the same set with the opposite
+    /// relation, leaving the `ParityDisjointSet` unchanged.
+    pub fn union(&mut self, value_one: T, value_two: T, relation: Parity) -> Result<UnionResult<T>, WeightedUnionError<Parity>> {
+        self.inner.union(value_one, value_two, relation)
+    }
+    
+    /// Unions the two sets that each value belongs to, recording that the values are on the same side.
+    pub fn union_same(&mut self, value_one: T, value_two: T) -> Result<UnionResult<T>, WeightedUnionError<Parity>> {
+        self.union(value_one, value_two, Parity::Same)
+    }
+    
+    /// Unions the two sets that each value belongs to, recording that the values are on opposite sides.
+    pub fn union_different(&mut self, value_one: T, value_two: T) -> Result<UnionResult<T>, WeightedUnionError<Parity>> {
+        self.union(value_one, value_two, Parity::Different)
+    }
+    
+    /// Returns the relation between the two values, performing path compression on both lookups.
+    ///
+    /// Returns `None` if the values are in different sets or one of them does not exist in the `ParityDisjointSet`.
+    pub fn relation<Q>(&mut self, value_one: &Q, value_two: &Q) -> Option<Parity>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.inner.diff(value_one, value_two)
+    }
+    
+    /// Checks whether the two values belong to the same set, performing path compression on both lookups.
+    ///
+    /// Returns `None` if one of the values does not exist in the `ParityDisjointSet`.
+    pub fn same_set<Q>(&mut self, value_one: &Q, value_two: &Q) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.inner.same_set(value_one, value_two)
+    }
+}
@@ -0,0 +1,151 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use crate::persistent_map::PersistentMap;
+use crate::UnionResult;
+
+/// A fully persistent disjoint-set: `make_set` and `union` return a new version and leave the old one valid.
+///
+/// Versions share structure through a hash array mapped trie, so cloning is O(1) and each operation only copies
+/// O(log n) nodes. Unions use union by rank without path compression, which would otherwise have to copy every path
+/// that a `find` visits.
+#[derive(Clone)]
+pub struct PersistentDisjointSet<T> {
+    nodes: PersistentMap<T, SubSet<T>>,
+    num_sets: usize
+}
+
+#[derive(Clone)]
+struct SubSet<T> {
+    parent: Option<T>,
+    rank: u32,
+    size: usize
+}
+
+impl<T> Default for PersistentDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    fn default() -> PersistentDisjointSet<T> {
+        PersistentDisjointSet::new()
+    }
+}
+
+impl<T> PersistentDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    pub fn new() -> PersistentDisjointSet<T> {
+        PersistentDisjointSet {
+            nodes: PersistentMap::new(),
+            num_sets: 0
+        }
+    }
+    
+    /// Returns a version with a singleton set of the value added.
+    ///
+    /// If the value is already in the `PersistentDisjointSet`, the returned version is identical to this one.
+    pub fn make_set(&self, value: T) -> PersistentDisjointSet<T> {
+        if self.nodes.get(&value).is_some() {
+            return self.clone();
+        }
+        PersistentDisjointSet {
+            nodes: self.nodes.insert(value, SubSet { parent: None, rank: 0, size: 1 }),
+            num_sets: self.num_sets + 1
+        }
+    }
+    
+    /// Returns the number of values in the `PersistentDisjointSet`.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    
+    /// Returns `true` if the `PersistentDisjointSet` contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.len() == 0
+    }
+    
+    /// Returns the number of disjoint sets in the `PersistentDisjointSet`.
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+    
+    /// Returns the number of values in the set that the value belongs to.
+    ///
+    /// Returns `None` if the value is not in the `PersistentDisjointSet`.
+    pub fn set_size<Q>(&self, value: &Q) -> Option<usize>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let (_, root) = self.find_node(value)?;
+        Some(root.size)
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to.
+    ///
+    /// Returns `None` if the value is not in the `PersistentDisjointSet`.
+    pub fn find<Q>(&self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.find_node(value).map(|(root, _)| root)
+    }
+    
+    /// Checks whether the two values belong to the same set.
+    ///
+    /// Returns `None` if one of the values does not exist in the `PersistentDisjointSet`.
+    pub fn same_set<Q>(&self, value_one: &Q, value_two: &Q) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let root_one = self.find(value_one)?;
+        let root_two = self.find(value_two)?;
+        Some(root_one == root_two)
+    }
+    
+    /// Returns a version in which the two sets that each value belongs to are unioned using union by rank, together
+    /// with the root of the resulting set and whether a merge happened.
+    ///
+    /// Returns `None` if one of the values does not exist in the `PersistentDisjointSet`.
+    pub fn union<Q>(&self, value_one: &Q, value_two: &Q) -> Option<(PersistentDisjointSet<T>, UnionResult<T>)>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let (root_one, node_one) = self.find_node(value_one)?;
+        let (root_two, node_two) = self.find_node(value_two)?;
+        
+        if root_one == root_two {
+            return Some((self.clone(), UnionResult::AlreadyJoined(root_one.clone())));
+        }
+        
+        let (root, root_node, child, child_node) = if node_one.rank < node_two.rank {
+            (root_two, node_two, root_one, node_one)
+        } else {
+            (root_one, node_one, root_two, node_two)
+        };
+        
+        let new_root = SubSet {
+            parent: None,
+            rank: if node_one.rank == node_two.rank { root_node.rank + 1 } else { root_node.rank },
+            size: root_node.size + child_node.size
+        };
+        let new_child = SubSet {
+            parent: Some(root.clone()),
+            rank: child_node.rank,
+            size: child_node.size
+        };
+        
+        let version = PersistentDisjointSet {
+            nodes: self.nodes.insert(root.clone(), new_root).insert(child.clone(), new_child),
+            num_sets: self.num_sets - 1
+        };
+        Some((version, UnionResult::Merged { root: root.clone(), absorbed: child.clone() }))
+    }
+    
+    /// Finds the root of the value together with its node.
+    fn find_node<Q>(&self, value: &Q) -> Option<(&T, &SubSet<T>)>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let (mut key, mut node) = self.nodes.get(value)?;
+        while let Some(ref parent) = node.parent {
+            let (parent_key, parent_node) = self.nodes.get::<T>(parent).unwrap();
+            key = parent_key;
+            node = parent_node;
+        }
+        Some((key, node))
+    }
+}
@@ -0,0 +1,129 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+/// An immutable hash array mapped trie. Updates copy only the path from the root to the changed entry and share every
+/// other node with the previous version, so each version costs O(log n) extra space.
+pub(crate) struct PersistentMap<K, V> {
+    root: Rc<Node<K, V>>,
+    len: usize
+}
+
+enum Node<K, V> {
+    /// Children are stored densely; bit `i` of `bitmap` says whether the child for hash chunk `i` is present.
+    Branch { bitmap: u32, children: Vec<Rc<Node<K, V>>> },
+    /// Every entry whose full hash is `hash`.
+    Leaf { hash: u64, entries: Vec<(K, V)> }
+}
+
+impl<K, V> Clone for PersistentMap<K, V> {
+    fn clone(&self) -> PersistentMap<K, V> {
+        PersistentMap {
+            root: self.root.clone(),
+            len: self.len
+        }
+    }
+}
+
+impl<K, V> PersistentMap<K, V>
+    where K: Eq + Hash + Clone, V: Clone
+{
+    pub(crate) fn new() -> PersistentMap<K, V> {
+        PersistentMap {
+            root: Rc::new(Node::Branch { bitmap: 0, children: Vec::new() }),
+            len: 0
+        }
+    }
+    
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+    
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<(&K, &V)>
+        where K: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let hash = hash_of(key);
+        let mut node = &self.root;
+        let mut shift = 0;
+        loop {
+            match **node {
+                Node::Branch { bitmap, ref children } => {
+                    let bit = 1 << ((hash >> shift) & MASK);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    node = &children[(bitmap & (bit - 1)).count_ones() as usize];
+                    shift += BITS;
+                }
+                Node::Leaf { hash: leaf_hash, ref entries } => {
+                    if leaf_hash != hash {
+                        return None;
+                    }
+                    return entries.iter().find(|entry| entry.0.borrow() == key).map(|entry| (&entry.0, &entry.1));
+                }
+            }
+        }
+    }
+    
+    /// Returns a new version of the map in which `key` maps to `value`.
+    pub(crate) fn insert(&self, key: K, value: V) -> PersistentMap<K, V> {
+        let hash = hash_of(&key);
+        let (root, added) = insert(&self.root, 0, hash, key, value);
+        PersistentMap {
+            root,
+            len: self.len + added as usize
+        }
+    }
+}
+
+/// Inserts into the subtree at `node`, returning the copied subtree and whether the key was new.
+fn insert<K, V>(node: &Rc<Node<K, V>>, shift: u32, hash: u64, key: K, value: V) -> (Rc<Node<K, V>>, bool)
+    where K: Eq + Clone, V: Clone
+{
+    match **node {
+        Node::Branch { bitmap, ref children } => {
+            let bit = 1 << ((hash >> shift) & MASK);
+            let position = (bitmap & (bit - 1)).count_ones() as usize;
+            let mut children = children.clone();
+            let added = if bitmap & bit == 0 {
+                children.insert(position, Rc::new(Node::Leaf { hash, entries: vec![(key, value)] }));
+                true
+            } else {
+                let (child, added) = insert(&children[position], shift + BITS, hash, key, value);
+                children[position] = child;
+                added
+            };
+            (Rc::new(Node::Branch { bitmap: bitmap | bit, children }), added)
+        }
+        Node::Leaf { hash: leaf_hash, ref entries } if leaf_hash == hash => {
+            let mut entries = entries.clone();
+            let added = match entries.iter_mut().find(|entry| entry.0 == key) {
+                Some(entry) => {
+                    entry.1 = value;
+                    false
+                }
+                None => {
+                    entries.push((key, value));
+                    true
+                }
+            };
+            (Rc::new(Node::Leaf { hash, entries }), added)
+        }
+        Node::Leaf { hash: leaf_hash, .. } => {
+            // Two different hashes always differ in some chunk, so pushing the leaf down a level eventually separates them.
+            let bit = 1 << ((leaf_hash >> shift) & MASK);
+            let branch = Rc::new(Node::Branch { bitmap: bit, children: vec![node.clone()] });
+            insert(&branch, shift, hash, key, value)
+        }
+    }
+}
+
+fn hash_of<Q: Hash + ?Sized>(key: &Q) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
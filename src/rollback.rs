@@ -0,0 +1,176 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{DenseDisjointSet, NoCompression, UnionResult};
+
+/// A disjoint-set that can be rolled back to an earlier state, for backtracking search.
+///
+/// Unions use union by rank without path compression, so every `find` is O(log n) and every change to the forest is
+/// a single parent link that can be undone in O(1).
+#[derive(Clone)]
+pub struct RollbackDisjointSet<T> {
+    indices: HashMap<T, usize>,
+    values: Vec<T>,
+    forest: DenseDisjointSet<NoCompression>,
+    history: Vec<(u64, Change)>,
+    serial: u64
+}
+
+/// A point in the history of a `RollbackDisjointSet` that it can later be rolled back to.
+///
+/// Rolling back to a checkpoint invalidates every checkpoint taken after it, even once the history has grown past them
+/// again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checkpoint {
+    history: usize,
+    serial: u64
+}
+
+#[derive(Clone, Copy)]
+enum Change {
+    MakeSet,
+    Link { root: usize, absorbed: usize, root_rank: u32 }
+}
+
+impl<T> Default for RollbackDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    fn default() -> RollbackDisjointSet<T> {
+        RollbackDisjointSet::new()
+    }
+}
+
+impl<T> RollbackDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    pub fn new() -> RollbackDisjointSet<T> {
+        RollbackDisjointSet {
+            indices: HashMap::new(),
+            values: Vec::new(),
+            forest: DenseDisjointSet::with_compression(0, NoCompression),
+            history: Vec::new(),
+            serial: 0
+        }
+    }
+    
+    /// Makes a singleton set of the value inside the `RollbackDisjointSet`.
+    ///
+    /// Returns `false` and leaves the existing set untouched if the value is already in the `RollbackDisjointSet`.
+    pub fn make_set(&mut self, value: T) -> bool {
+        if self.indices.contains_key(&value) {
+            return false;
+        }
+        let index = self.forest.make_set();
+        self.indices.insert(value.clone(), index);
+        self.values.push(value);
+        self.record(Change::MakeSet);
+        true
+    }
+    
+    /// Returns the number of values in the `RollbackDisjointSet`.
+    pub fn len(&self) -> usize {
+        self.forest.len()
+    }
+    
+    /// Returns `true` if the `RollbackDisjointSet` contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.forest.is_empty()
+    }
+    
+    /// Returns the number of disjoint sets in the `RollbackDisjointSet`.
+    pub fn num_sets(&self) -> usize {
+        self.forest.num_sets()
+    }
+    
+    /// Returns the number of values in the set that the value belongs to.
+    ///
+    /// Returns `None` if the value is not in the `RollbackDisjointSet`.
+    pub fn set_size<Q>(&self, value: &Q) -> Option<usize>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        Some(self.forest.size(self.forest.root_of(index)))
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to.
+    ///
+    /// Returns `None` if the value is not in the `RollbackDisjointSet`.
+    pub fn find<Q>(&self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        Some(&self.values[self.forest.root_of(index)])
+    }
+    
+    /// Unions the two sets that each value belongs to using union by rank.
+    ///
+    /// Returns `None` if one of the values does not exist in the `RollbackDisjointSet`, otherwise reports the root of
+    /// the resulting set and whether a merge happened.
+    pub fn union(&mut self, value_one: T, value_two: T) -> Option<UnionResult<T>> {
+        let index_one = *self.indices.get(&value_one)?;
+        let index_two = *self.indices.get(&value_two)?;
+        let root_one = self.forest.root_of(index_one);
+        let root_two = self.forest.root_of(index_two);
+        
+        let root_one_rank = self.forest.rank(root_one);
+        let root_two_rank = self.forest.rank(root_two);
+        let result = self.forest.union_roots(root_one, root_two);
+        if let UnionResult::Merged { root, absorbed } = result {
+            let root_rank = if root == root_one { root_one_rank } else { root_two_rank };
+            self.record(Change::Link { root, absorbed, root_rank });
+        }
+        Some(result.map(|index| self.values[index].clone()))
+    }
+    
+    /// Checks whether the two values belong to the same set.
+    ///
+    /// Returns `None` if one of the values does not exist in the `RollbackDisjointSet`.
+    pub fn same_set<Q>(&self, value_one: &Q, value_two: &Q) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index_one = *self.indices.get(value_one)?;
+        let index_two = *self.indices.get(value_two)?;
+        Some(self.forest.root_of(index_one) == self.forest.root_of(index_two))
+    }
+    
+    /// Returns a checkpoint of the current state that can later be passed to `rollback`.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            history: self.history.len(),
+            serial: self.history.last().map_or(0, |&(serial, _)| serial)
+        }
+    }
+    
+    /// Undoes every `make_set` and `union` performed since the checkpoint was taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the checkpoint has been invalidated, i.e. it was taken after an earlier checkpoint that has since been
+    /// rolled back to.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        // Serials are never reused, so the change a checkpoint ends on is only still there if no rollback discarded it.
+        let current = match checkpoint.history {
+            0 => 0,
+            len => self.history.get(len - 1).map_or(u64::MAX, |&(serial, _)| serial)
+        };
+        assert!(current == checkpoint.serial, "checkpoint has been invalidated by an earlier rollback");
+        
+        while self.history.len() > checkpoint.history {
+            match self.history.pop().unwrap().1 {
+                Change::MakeSet => {
+                    let value = self.values.pop().unwrap();
+                    self.indices.remove(&value);
+                    self.forest.pop_set();
+                }
+                Change::Link { root, absorbed, root_rank } => self.forest.unlink(root, absorbed, root_rank)
+            }
+        }
+    }
+    
+    /// Appends a change to the history under a fresh serial.
+    fn record(&mut self, change: Change) {
+        self.serial += 1;
+        self.history.push((self.serial, change));
+    }
+}
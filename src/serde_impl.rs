@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::dense::resolve_roots;
+use crate::{Compression, DisjointSet, Linking};
+
+/// The serialized form of a `DisjointSet`: every value with its parent and rank, and the data of every root.
+#[derive(Serialize, Deserialize)]
+struct Partition<T, D> {
+    nodes: Vec<Node<T>>,
+    sets: Vec<SetData<T, D>>
+}
+
+/// A value with its parent, which is the value itself for a root.
+#[derive(Serialize, Deserialize)]
+struct Node<T> {
+    value: T,
+    parent: T,
+    rank: u32
+}
+
+/// The data of the set whose root is `root`.
+#[derive(Serialize, Deserialize)]
+struct SetData<T, D> {
+    root: T,
+    data: D
+}
+
+impl<T, D, C, L> Serialize for DisjointSet<T, D, C, L>
+    where T: Serialize + Eq + Hash + Clone, D: Serialize, C: Compression, L: Linking<T>
+{
+    /// Serializes every value pointing directly at the root of its set, since the nodes on the path between them may
+    /// be vacant. Ranks are adjusted to the flattened forest, so they stay below the number of values.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes = Vec::with_capacity(self.len());
+        let mut sets = Vec::with_capacity(self.num_sets());
+        for (index, value) in self.values.iter().enumerate() {
+            let value = match *value {
+                Some(ref value) => value,
+                None => continue
+            };
+            let root = self.forest.root_of(index);
+            nodes.push(Node { value, parent: self.value(root), rank: self.flattened_rank(index) });
+            if root == index {
+                sets.push(SetData { root: value, data: self.data[index].as_ref().unwrap() });
+            }
+        }
+        Partition { nodes, sets }.serialize(serializer)
+    }
+}
+
+impl<'de, T, D, C, L> Deserialize<'de> for DisjointSet<T, D, C, L>
+    where T: Deserialize<'de> + Eq + Hash + Clone, D: Deserialize<'de>, C: Compression + Default, L: Linking<T> + Default
+{
+    /// Deserializes any forest of parent pointers, flattening every tree under its root. The rank of every root is
+    /// raised if needed so that it stays above the ranks of its children.
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let Partition { nodes, sets } = Partition::<T, D>::deserialize(deserializer)?;
+        
+        let mut indices = HashMap::with_capacity(nodes.len());
+        for (index, node) in nodes.iter().enumerate() {
+            if indices.insert(node.value.clone(), index).is_some() {
+                return Err(De::Error::custom(format_args!("value at position {} appears more than once", index)));
+            }
+        }
+        let mut parents = Vec::with_capacity(nodes.len());
+        for (index, node) in nodes.iter().enumerate() {
+            // A rank never exceeds the height of its tree, so anything larger is corrupt and could overflow on union.
+            if node.rank as usize >= nodes.len() {
+                return Err(De::Error::custom(format_args!("rank of value at position {} is out of bounds", index)));
+            }
+            match indices.get(&node.parent) {
+                Some(&parent) => parents.push(parent),
+                None => return Err(De::Error::custom(format_args!("parent of value at position {} is not in the set", index)))
+            }
+        }
+        let roots = resolve_roots(&parents).map_err(|index| {
+            De::Error::custom(format_args!("parent of value at position {} leads into a cycle", index))
+        })?;
+        
+        let mut data: Vec<Option<D>> = (0..nodes.len()).map(|_| None).collect();
+        for set in sets {
+            let index = match indices.get(&set.root) {
+                Some(&index) if roots[index] == index => index,
+                _ => return Err(De::Error::custom("set data is attached to a value that is not a root"))
+            };
+            if data[index].replace(set.data).is_some() {
+                return Err(De::Error::custom(format_args!("value at position {} has data attached more than once", index)));
+            }
+        }
+        if let Some(index) = (0..nodes.len()).find(|&index| roots[index] == index && data[index].is_none()) {
+            return Err(De::Error::custom(format_args!("root at position {} has no set data", index)));
+        }
+        
+        let mut disjoint_set = DisjointSet::with_strategies(C::default(), L::default());
+        for node in nodes {
+            let index = disjoint_set.forest.make_set();
+            disjoint_set.forest.set_rank(index, node.rank);
+            disjoint_set.values.push(Some(node.value));
+        }
+        for (index, &root) in roots.iter().enumerate() {
+            if root != index {
+                disjoint_set.forest.link(root, index);
+            }
+        }
+        disjoint_set.indices = indices;
+        disjoint_set.data = data;
+        Ok(disjoint_set)
+    }
+}
@@ -0,0 +1,486 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{self, Read, Write};
+
+use crate::dense::resolve_roots;
+use crate::{ByRank, Compression, DenseDisjointSet, DisjointSet, Linking};
+
+const MAGIC: [u8; 4] = *b"DSET";
+const VERSION: u16 = 1;
+const HEADER_LEN: usize = 24;
+const CHECKSUM_LEN: usize = 8;
+const RANK_WIDTH: usize = 4;
+
+const KIND_DENSE: u8 = 0;
+const KIND_KEYED: u8 = 1;
+
+/// The number of bytes read or written at a time while copying the parent and rank tables.
+const CHUNK_LEN: usize = 1 << 16;
+/// The most elements allocated for ahead of reading them, so that a corrupt header cannot exhaust memory.
+const MAX_PREALLOCATION: usize = 1 << 20;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A value that can be stored in the element and set data tables of a snapshot.
+///
+/// Integers are stored little-endian at their full width and strings as a `u64` byte length followed by UTF-8.
+pub trait SnapshotValue: Sized {
+    /// Writes the value to the snapshot.
+    fn write_value<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+    
+    /// Reads a value written by `write_value` from the snapshot.
+    fn read_value<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! integer_snapshot_value {
+    ($($t:ty)*) => {$(
+        impl SnapshotValue for $t {
+            fn write_value<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
+            
+            fn read_value<R: Read>(reader: &mut R) -> io::Result<$t> {
+                let mut bytes = [0; std::mem::size_of::<$t>()];
+                reader.read_exact(&mut bytes)?;
+                Ok(<$t>::from_le_bytes(bytes))
+            }
+        }
+    )*}
+}
+
+integer_snapshot_value! { i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 }
+
+impl SnapshotValue for usize {
+    fn write_value<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u64).write_value(writer)
+    }
+    
+    fn read_value<R: Read>(reader: &mut R) -> io::Result<usize> {
+        usize::try_from(u64::read_value(reader)?).map_err(|_| invalid("value does not fit in usize"))
+    }
+}
+
+impl SnapshotValue for String {
+    fn write_value<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (self.len() as u64).write_value(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+    
+    fn read_value<R: Read>(reader: &mut R) -> io::Result<String> {
+        let len = u64::read_value(reader)?;
+        let mut bytes = Vec::new();
+        reader.take(len).read_to_end(&mut bytes)?;
+        if (bytes.len() as u64) < len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        String::from_utf8(bytes).map_err(|_| invalid("string is not valid UTF-8"))
+    }
+}
+
+impl SnapshotValue for () {
+    fn write_value<W: Write>(&self, _: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+    
+    fn read_value<R: Read>(_: &mut R) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The fixed-size header at the start of every snapshot.
+struct Header {
+    kind: u8,
+    index_width: usize,
+    len: usize,
+    num_sets: usize
+}
+
+impl Header {
+    /// Describes a snapshot of `len` elements, storing parent indices in 32 bits whenever they fit.
+    fn new(kind: u8, len: usize, num_sets: usize) -> Header {
+        let index_width = if len <= u32::MAX as usize { 4 } else { 8 };
+        Header { kind, index_width, len, num_sets }
+    }
+    
+    fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0; HEADER_LEN];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4..6].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[6] = self.kind;
+        bytes[7] = self.index_width as u8;
+        bytes[8..16].copy_from_slice(&(self.len as u64).to_le_bytes());
+        bytes[16..24].copy_from_slice(&(self.num_sets as u64).to_le_bytes());
+        bytes
+    }
+    
+    fn parse(bytes: &[u8; HEADER_LEN], kind: u8) -> io::Result<Header> {
+        if bytes[0..4] != MAGIC {
+            return Err(invalid("not a disjoint set snapshot"));
+        }
+        if read_word(&bytes[4..6]) != VERSION as u64 {
+            return Err(invalid("unsupported snapshot version"));
+        }
+        if bytes[6] != kind {
+            return Err(invalid(if kind == KIND_DENSE {
+                "snapshot does not hold a DenseDisjointSet"
+            } else {
+                "snapshot does not hold a DisjointSet"
+            }));
+        }
+        let index_width = bytes[7] as usize;
+        if index_width != 4 && index_width != 8 {
+            return Err(invalid("unsupported parent index width"));
+        }
+        let len = usize::try_from(read_word(&bytes[8..16])).map_err(|_| invalid("snapshot is too large"))?;
+        let num_sets = usize::try_from(read_word(&bytes[16..24])).map_err(|_| invalid("snapshot is too large"))?;
+        Ok(Header { kind, index_width, len, num_sets })
+    }
+    
+    /// Returns the length of the snapshot of a `DenseDisjointSet` described by the header, checksum included.
+    fn dense_len(&self) -> Option<usize> {
+        self.len.checked_mul(self.index_width + RANK_WIDTH)?.checked_add(HEADER_LEN + CHECKSUM_LEN)
+    }
+}
+
+/// A reader or writer that keeps an FNV-1a hash of every byte passed through it.
+struct Checksummed<I> {
+    inner: I,
+    hash: u64
+}
+
+impl<I> Checksummed<I> {
+    fn new(inner: I) -> Checksummed<I> {
+        Checksummed { inner, hash: FNV_OFFSET }
+    }
+}
+
+impl<W: Write> Checksummed<W> {
+    /// Appends the checksum of everything written so far.
+    fn write_checksum(mut self) -> io::Result<()> {
+        self.inner.write_all(&self.hash.to_le_bytes())?;
+        self.inner.flush()
+    }
+}
+
+impl<R: Read> Checksummed<R> {
+    /// Reads the stored checksum and compares it with the checksum of everything read so far.
+    fn verify_checksum(mut self) -> io::Result<()> {
+        let mut bytes = [0; CHECKSUM_LEN];
+        self.inner.read_exact(&mut bytes)?;
+        if u64::from_le_bytes(bytes) != self.hash {
+            return Err(invalid("snapshot checksum does not match"));
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Checksummed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hash = checksum(self.hash, &buf[..written]);
+        Ok(written)
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: Read> Read for Checksummed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hash = checksum(self.hash, &buf[..read]);
+        Ok(read)
+    }
+}
+
+impl<C, L> DenseDisjointSet<C, L>
+    where C: Compression, L: Linking<usize>
+{
+    /// Writes a snapshot of the `DenseDisjointSet`, which keeps the exact shape of the forest.
+    ///
+    /// The snapshot is a 24-byte header, the parent of every element as a little-endian 32-bit index (64-bit once
+    /// there are more than `u32::MAX` elements), the rank of every element as a little-endian `u32` and an FNV-1a
+    /// checksum of everything before it. Since every table has a fixed width, a snapshot can also be memory-mapped and
+    /// queried in place through `DenseSnapshot`.
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        let header = Header::new(KIND_DENSE, self.len(), self.num_sets());
+        let mut writer = Checksummed::new(writer);
+        write_forest(&mut writer, &header, |index| (self.parent(index), self.rank(index)))?;
+        writer.write_checksum()
+    }
+    
+    /// Reads a snapshot written by `DenseDisjointSet::write_to`, using the default strategies.
+    ///
+    /// Returns an error of kind `InvalidData` if the snapshot is malformed or its checksum does not match.
+    pub fn read_from<R: Read>(reader: R) -> io::Result<DenseDisjointSet<C, L>>
+        where C: Default, L: Default
+    {
+        let mut reader = Checksummed::new(reader);
+        let header = read_header(&mut reader, KIND_DENSE)?;
+        let (parents, ranks, roots) = read_forest(&mut reader, &header)?;
+        reader.verify_checksum()?;
+        Ok(DenseDisjointSet::from_parts(parents, ranks, &roots, C::default(), L::default()))
+    }
+}
+
+impl<T, D, C, L> DisjointSet<T, D, C, L>
+    where T: Eq + Hash + Clone, C: Compression, L: Linking<T>
+{
+    /// Writes a snapshot of the `DisjointSet`, with every value pointing directly at the root of its set and ranks
+    /// adjusted to the flattened forest.
+    ///
+    /// The snapshot starts with the same header, parent table and rank table as `DenseDisjointSet::write_to`, indexed
+    /// by position in the element table, which follows them along with the data of every set in order of their roots,
+    /// and ends with the checksum. Values are written one at a time, so the writer should be buffered.
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()>
+        where T: SnapshotValue, D: SnapshotValue
+    {
+        // Vacant nodes are left out, so every occupied node is renumbered by its position among them.
+        let mut positions = vec![usize::MAX; self.values.len()];
+        let occupied: Vec<usize> = (0..self.values.len()).filter(|&index| self.values[index].is_some()).collect();
+        for (position, &index) in occupied.iter().enumerate() {
+            positions[index] = position;
+        }
+        
+        let header = Header::new(KIND_KEYED, occupied.len(), self.num_sets());
+        let mut writer = Checksummed::new(writer);
+        write_forest(&mut writer, &header, |position| {
+            let index = occupied[position];
+            (positions[self.forest.root_of(index)], self.flattened_rank(index))
+        })?;
+        for &index in &occupied {
+            self.value(index).write_value(&mut writer)?;
+        }
+        for &index in &occupied {
+            if let Some(ref data) = self.data[index] {
+                data.write_value(&mut writer)?;
+            }
+        }
+        writer.write_checksum()
+    }
+    
+    /// Reads a snapshot written by `DisjointSet::write_to`, using the default strategies.
+    ///
+    /// Returns an error of kind `InvalidData` if the snapshot is malformed, contains a value more than once or its
+    /// checksum does not match.
+    pub fn read_from<R: Read>(reader: R) -> io::Result<DisjointSet<T, D, C, L>>
+        where T: SnapshotValue, D: SnapshotValue, C: Default, L: Default
+    {
+        let mut reader = Checksummed::new(reader);
+        let header = read_header(&mut reader, KIND_KEYED)?;
+        let (parents, ranks, roots) = read_forest(&mut reader, &header)?;
+        
+        let mut indices = HashMap::with_capacity(header.len.min(MAX_PREALLOCATION));
+        let mut values = Vec::with_capacity(header.len.min(MAX_PREALLOCATION));
+        for index in 0..header.len {
+            let value = T::read_value(&mut reader)?;
+            if indices.insert(value.clone(), index).is_some() {
+                return Err(invalid("snapshot contains a value more than once"));
+            }
+            values.push(Some(value));
+        }
+        let mut data = Vec::with_capacity(header.len.min(MAX_PREALLOCATION));
+        for (index, &root) in roots.iter().enumerate() {
+            data.push(if root == index { Some(D::read_value(&mut reader)?) } else { None });
+        }
+        reader.verify_checksum()?;
+        
+        Ok(DisjointSet {
+            indices,
+            values,
+            data,
+            forest: DenseDisjointSet::from_parts(parents, ranks, &roots, C::default(), ByRank),
+            linking: L::default(),
+            vacant: 0
+        })
+    }
+}
+
+/// A read-only view of a snapshot written by `DenseDisjointSet::write_to`, answering queries straight from its bytes.
+///
+/// The bytes can come from a memory-mapped file, so a large snapshot can be queried without reading it into memory.
+/// Since the view cannot compress paths, `find` takes time proportional to the height of the tree, which union by rank
+/// keeps logarithmic.
+#[derive(Clone, Copy)]
+pub struct DenseSnapshot<'a> {
+    len: usize,
+    num_sets: usize,
+    index_width: usize,
+    parents: &'a [u8]
+}
+
+impl<'a> DenseSnapshot<'a> {
+    /// Checks the snapshot and creates a view of it.
+    ///
+    /// Returns an error of kind `InvalidData` if the snapshot is malformed or its checksum does not match. Checking
+    /// reads every byte once but allocates nothing.
+    pub fn from_bytes(bytes: &'a [u8]) -> io::Result<DenseSnapshot<'a>> {
+        if bytes.len() < HEADER_LEN {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let header = Header::parse(bytes[..HEADER_LEN].try_into().unwrap(), KIND_DENSE)?;
+        match header.dense_len() {
+            Some(len) if len == bytes.len() => {}
+            Some(len) if len > bytes.len() => return Err(io::ErrorKind::UnexpectedEof.into()),
+            _ => return Err(invalid("snapshot is longer than its header describes"))
+        }
+        let (contents, stored) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if checksum(FNV_OFFSET, contents) != read_word(stored) {
+            return Err(invalid("snapshot checksum does not match"));
+        }
+        
+        let (parents, ranks) = contents[HEADER_LEN..].split_at(header.len * header.index_width);
+        let parent = |index: usize| read_word(&parents[index * header.index_width..][..header.index_width]);
+        let rank = |index: usize| read_word(&ranks[index * RANK_WIDTH..][..RANK_WIDTH]);
+        let mut num_roots = 0;
+        for index in 0..header.len {
+            check_node(index, parent(index), rank(index), header.len, |parent| rank(parent) > rank(index))?;
+            if parent(index) == index as u64 {
+                num_roots += 1;
+            }
+        }
+        if num_roots != header.num_sets {
+            return Err(invalid("snapshot header disagrees with the number of sets"));
+        }
+        
+        Ok(DenseSnapshot { len: header.len, num_sets: header.num_sets, index_width: header.index_width, parents })
+    }
+    
+    /// Returns the number of elements in the snapshot.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    /// Returns `true` if the snapshot contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    /// Returns the number of disjoint sets in the snapshot.
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+    
+    /// Finds the root of the set that the element belongs to.
+    ///
+    /// Returns `None` if the element is not in the snapshot.
+    pub fn find(&self, element: usize) -> Option<usize> {
+        if element >= self.len {
+            return None;
+        }
+        let mut root = element;
+        loop {
+            let parent = read_word(&self.parents[root * self.index_width..][..self.index_width]) as usize;
+            if parent == root {
+                return Some(root);
+            }
+            root = parent;
+        }
+    }
+    
+    /// Checks whether the two elements belong to the same set.
+    ///
+    /// Returns `None` if one of the elements does not exist in the snapshot.
+    pub fn same_set(&self, element_one: usize, element_two: usize) -> Option<bool> {
+        Some(self.find(element_one)? == self.find(element_two)?)
+    }
+}
+
+/// Writes the header followed by the parent and rank tables of `header.len` elements.
+fn write_forest<W, F>(writer: &mut W, header: &Header, node: F) -> io::Result<()>
+    where W: Write, F: Fn(usize) -> (usize, u32)
+{
+    writer.write_all(&header.to_bytes())?;
+    write_words(writer, (0..header.len).map(|index| node(index).0 as u64), header.index_width)?;
+    write_words(writer, (0..header.len).map(|index| node(index).1 as u64), RANK_WIDTH)
+}
+
+fn read_header<R: Read>(reader: &mut R, kind: u8) -> io::Result<Header> {
+    let mut bytes = [0; HEADER_LEN];
+    reader.read_exact(&mut bytes)?;
+    Header::parse(&bytes, kind)
+}
+
+/// Reads the parent and rank tables described by the header and finds the root of every element.
+fn read_forest<R: Read>(reader: &mut R, header: &Header) -> io::Result<(Vec<usize>, Vec<u32>, Vec<usize>)> {
+    let mut parents = Vec::with_capacity(header.len.min(MAX_PREALLOCATION));
+    read_words(reader, header.len, header.index_width, |word| parents.push(word as usize))?;
+    let mut ranks = Vec::with_capacity(header.len.min(MAX_PREALLOCATION));
+    read_words(reader, header.len, RANK_WIDTH, |word| ranks.push(word as u32))?;
+    
+    for (index, &parent) in parents.iter().enumerate() {
+        check_node(index, parent as u64, ranks[index] as u64, header.len, |parent| ranks[parent] > ranks[index])?;
+    }
+    // Ranks strictly increase towards the roots, so the forest cannot contain a cycle.
+    let roots = resolve_roots(&parents).unwrap();
+    if roots.iter().enumerate().filter(|&(index, &root)| root == index).count() != header.num_sets {
+        return Err(invalid("snapshot header disagrees with the number of sets"));
+    }
+    Ok((parents, ranks, roots))
+}
+
+/// Checks that the rank of an element is below the number of elements, which bounds the height of any tree, and that
+/// its parent is either the element itself or an element of higher rank, which rules out dangling parents and cycles.
+fn check_node<F>(index: usize, parent: u64, rank: u64, len: usize, outranks: F) -> io::Result<()>
+    where F: Fn(usize) -> bool
+{
+    if rank >= len as u64 {
+        return Err(invalid("snapshot contains a rank that is out of bounds"));
+    }
+    if parent >= len as u64 {
+        return Err(invalid("snapshot contains a parent that is out of bounds"));
+    }
+    if parent != index as u64 && !outranks(parent as usize) {
+        return Err(invalid("snapshot contains a parent that does not outrank its child"));
+    }
+    Ok(())
+}
+
+/// Writes each word little-endian in `width` bytes, in chunks.
+fn write_words<W, I>(writer: &mut W, words: I, width: usize) -> io::Result<()>
+    where W: Write, I: Iterator<Item = u64>
+{
+    let mut chunk = Vec::with_capacity(CHUNK_LEN);
+    for word in words {
+        chunk.extend_from_slice(&word.to_le_bytes()[..width]);
+        if chunk.len() + width > CHUNK_LEN {
+            writer.write_all(&chunk)?;
+            chunk.clear();
+        }
+    }
+    writer.write_all(&chunk)
+}
+
+/// Reads `count` little-endian words of `width` bytes, in chunks.
+fn read_words<R, F>(reader: &mut R, count: usize, width: usize, mut push: F) -> io::Result<()>
+    where R: Read, F: FnMut(u64)
+{
+    let mut chunk = vec![0; CHUNK_LEN - CHUNK_LEN % width];
+    let mut remaining = count;
+    while remaining > 0 {
+        let words = remaining.min(chunk.len() / width);
+        let bytes = &mut chunk[..words * width];
+        reader.read_exact(bytes)?;
+        bytes.chunks_exact(width).for_each(|word| push(read_word(word)));
+        remaining -= words;
+    }
+    Ok(())
+}
+
+/// Reads a little-endian word of up to eight bytes.
+fn read_word(bytes: &[u8]) -> u64 {
+    let mut word = [0; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+/// Continues an FNV-1a hash over the bytes.
+fn checksum(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
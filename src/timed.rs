@@ -0,0 +1,191 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::UnionResult;
+
+/// A partially persistent disjoint-set that can answer connectivity queries about any earlier point in its history.
+///
+/// Time is measured in unions: time `t` is the state after the first `t` calls to `union` that found both of their
+/// values, so time `0` is before any union. Unions use union by rank without path compression and stamp every parent
+/// link with the time it was made. Following only the links made by time `t` gives the forest as it was at time `t`,
+/// which makes every query O(log n). Values are treated as present at every time, regardless of when they were added.
+#[derive(Clone)]
+pub struct TimedDisjointSet<T> {
+    indices: HashMap<T, usize>,
+    values: Vec<T>,
+    parents: Vec<usize>,
+    ranks: Vec<u32>,
+    times: Vec<usize>,
+    time: usize
+}
+
+/// The link time of a root, later than every real union.
+const NEVER: usize = usize::MAX;
+
+impl<T> Default for TimedDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    fn default() -> TimedDisjointSet<T> {
+        TimedDisjointSet::new()
+    }
+}
+
+impl<T> TimedDisjointSet<T>
+    where T: Eq + Hash + Clone
+{
+    pub fn new() -> TimedDisjointSet<T> {
+        TimedDisjointSet {
+            indices: HashMap::new(),
+            values: Vec::new(),
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            times: Vec::new(),
+            time: 0
+        }
+    }
+    
+    /// Makes a singleton set of the value inside the `TimedDisjointSet`.
+    ///
+    /// Returns `false` and leaves the existing set untouched if the value is already in the `TimedDisjointSet`.
+    pub fn make_set(&mut self, value: T) -> bool {
+        if self.indices.contains_key(&value) {
+            return false;
+        }
+        let index = self.values.len();
+        self.indices.insert(value.clone(), index);
+        self.values.push(value);
+        self.parents.push(index);
+        self.ranks.push(0);
+        self.times.push(NEVER);
+        true
+    }
+    
+    /// Returns the number of values in the `TimedDisjointSet`.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    
+    /// Returns `true` if the `TimedDisjointSet` contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    
+    /// Returns the current time, which is the number of unions performed so far.
+    pub fn time(&self) -> usize {
+        self.time
+    }
+    
+    /// Finds the value of the root of the set that the value currently belongs to.
+    ///
+    /// Returns `None` if the value is not in the `TimedDisjointSet`.
+    pub fn find<Q>(&self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.find_at(value, self.time)
+    }
+    
+    /// Finds the value of the root of the set that the value belonged to at time `time`.
+    ///
+    /// Returns `None` if the value is not in the `TimedDisjointSet`.
+    pub fn find_at<Q>(&self, value: &Q, time: usize) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        Some(&self.values[self.root_at(index, time)])
+    }
+    
+    /// Unions the two sets that each value belongs to using union by rank and advances the time by one.
+    ///
+    /// Returns `None` and leaves the time unchanged if one of the values does not exist in the `TimedDisjointSet`,
+    /// otherwise reports the root of the resulting set and whether a merge happened.
+    pub fn union(&mut self, value_one: T, value_two: T) -> Option<UnionResult<T>> {
+        let index_one = *self.indices.get(&value_one)?;
+        let index_two = *self.indices.get(&value_two)?;
+        
+        self.time += 1;
+        let root_one = self.root_at(index_one, self.time);
+        let root_two = self.root_at(index_two, self.time);
+        
+        if root_one == root_two {
+            return Some(UnionResult::AlreadyJoined(self.values[root_one].clone()));
+        }
+        
+        let root_one_rank = self.ranks[root_one];
+        let root_two_rank = self.ranks[root_two];
+        
+        let (root, child) = if root_one_rank < root_two_rank {
+            (root_two, root_one)
+        } else {
+            if root_one_rank == root_two_rank {
+                self.ranks[root_one] = root_one_rank + 1;
+            }
+            (root_one, root_two)
+        };
+        
+        self.parents[child] = root;
+        self.times[child] = self.time;
+        Some(UnionResult::Merged {
+            root: self.values[root].clone(),
+            absorbed: self.values[child].clone()
+        })
+    }
+    
+    /// Checks whether the two values currently belong to the same set.
+    ///
+    /// Returns `None` if one of the values does not exist in the `TimedDisjointSet`.
+    pub fn same_set<Q>(&self, value_one: &Q, value_two: &Q) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.connected_at(value_one, value_two, self.time)
+    }
+    
+    /// Checks whether the two values belonged to the same set at time `time`, i.e. after the first `time` unions.
+    ///
+    /// Returns `None` if one of the values does not exist in the `TimedDisjointSet`.
+    pub fn connected_at<Q>(&self, value_one: &Q, value_two: &Q, time: usize) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index_one = *self.indices.get(value_one)?;
+        let index_two = *self.indices.get(value_two)?;
+        Some(self.root_at(index_one, time) == self.root_at(index_two, time))
+    }
+    
+    /// Returns the earliest time at which the two values belonged to the same set, which is `0` for a value and
+    /// itself.
+    ///
+    /// Returns `None` if the values are not currently connected or one of them does not exist in the
+    /// `TimedDisjointSet`.
+    pub fn first_connected<Q>(&self, value_one: &Q, value_two: &Q) -> Option<usize>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let mut node_one = *self.indices.get(value_one)?;
+        let mut node_two = *self.indices.get(value_two)?;
+        if self.root_at(node_one, self.time) != self.root_at(node_two, self.time) {
+            return None;
+        }
+        
+        // Link times strictly increase towards the root, so repeatedly climbing from the node with the earlier link
+        // meets at the lowest common ancestor, and the latest link crossed is when the two first became connected.
+        let mut time = 0;
+        while node_one != node_two {
+            if self.times[node_one] < self.times[node_two] {
+                time = time.max(self.times[node_one]);
+                node_one = self.parents[node_one];
+            } else {
+                time = time.max(self.times[node_two]);
+                node_two = self.parents[node_two];
+            }
+        }
+        Some(time)
+    }
+    
+    /// Finds the root of `index` in the forest as it was at time `time`.
+    fn root_at(&self, index: usize, time: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root && self.times[root] <= time {
+            root = self.parents[root];
+        }
+        root
+    }
+}
@@ -0,0 +1,251 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::UnionResult;
+
+/// A commutative group, used as the type of the offsets recorded by a `WeightedDisjointSet`.
+pub trait Group: Clone + PartialEq {
+    /// The identity element, i.e. the offset between a value and itself.
+    fn identity() -> Self;
+    
+    /// Combines two offsets.
+    fn combine(&self, other: &Self) -> Self;
+    
+    /// Returns the offset that combines with this one to give the identity.
+    fn inverse(&self) -> Self;
+}
+
+macro_rules! integer_group {
+    ($($t:ty)*) => {$(
+        /// Addition with wrapping on overflow, which keeps the group laws exact.
+        impl Group for $t {
+            fn identity() -> $t {
+                0
+            }
+            
+            fn combine(&self, other: &$t) -> $t {
+                self.wrapping_add(*other)
+            }
+            
+            fn inverse(&self) -> $t {
+                self.wrapping_neg()
+            }
+        }
+    )*}
+}
+
+integer_group! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+/// The reason a `WeightedDisjointSet::union` was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WeightedUnionError<W> {
+    /// One of the values does not exist in the `WeightedDisjointSet`.
+    MissingValue,
+    /// The values are already in the same set with a different offset, contained here.
+    Inconsistent { existing: W }
+}
+
+impl<W: fmt::Debug> fmt::Display for WeightedUnionError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WeightedUnionError::MissingValue => write!(f, "value is not in the set"),
+            WeightedUnionError::Inconsistent { ref existing } => {
+                write!(f, "values are already related by a different offset {:?}", existing)
+            }
+        }
+    }
+}
+
+impl<W: fmt::Debug> Error for WeightedUnionError<W> {}
+
+/// A disjoint-set that also records the offset between values in the same set, for relations of the form
+/// `value(b) - value(a) = w`.
+///
+/// Every node stores its offset from its parent, so the offset from a node to its root is the combination of the
+/// offsets along the path. Path compression re-points nodes at the root and replaces their offset with that sum.
+#[derive(Clone)]
+pub struct WeightedDisjointSet<T, W> {
+    indices: HashMap<T, usize>,
+    values: Vec<T>,
+    parents: Vec<usize>,
+    ranks: Vec<u32>,
+    weights: Vec<W>,
+    num_sets: usize
+}
+
+impl<T, W> Default for WeightedDisjointSet<T, W>
+    where T: Eq + Hash + Clone, W: Group
+{
+    fn default() -> WeightedDisjointSet<T, W> {
+        WeightedDisjointSet::new()
+    }
+}
+
+impl<T, W> WeightedDisjointSet<T, W>
+    where T: Eq + Hash + Clone, W: Group
+{
+    pub fn new() -> WeightedDisjointSet<T, W> {
+        WeightedDisjointSet {
+            indices: HashMap::new(),
+            values: Vec::new(),
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            weights: Vec::new(),
+            num_sets: 0
+        }
+    }
+    
+    /// Makes a singleton set of the value inside the `WeightedDisjointSet`.
+    ///
+    /// Returns `false` and leaves the existing set untouched if the value is already in the `WeightedDisjointSet`.
+    pub fn make_set(&mut self, value: T) -> bool {
+        if self.indices.contains_key(&value) {
+            return false;
+        }
+        let index = self.values.len();
+        self.indices.insert(value.clone(), index);
+        self.values.push(value);
+        self.parents.push(index);
+        self.ranks.push(0);
+        self.weights.push(W::identity());
+        self.num_sets += 1;
+        true
+    }
+    
+    /// Returns the number of values in the `WeightedDisjointSet`.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    
+    /// Returns `true` if the `WeightedDisjointSet` contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    
+    /// Returns the number of disjoint sets in the `WeightedDisjointSet`.
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to without modifying the `WeightedDisjointSet`.
+    ///
+    /// Returns `None` if the value is not in the `WeightedDisjointSet`.
+    pub fn find<Q>(&self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let mut root = *self.indices.get(value)?;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        Some(&self.values[root])
+    }
+    
+    /// Finds the value of the root of the set that the value belongs to and performs path compression on the visited nodes.
+    ///
+    /// Returns `None` if the value is not in the `WeightedDisjointSet`.
+    pub fn find_mut<Q>(&mut self, value: &Q) -> Option<&T>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index = *self.indices.get(value)?;
+        let (root, _) = self.find_root(index);
+        Some(&self.values[root])
+    }
+    
+    /// Unions the two sets that each value belongs to using union by rank, recording that
+    /// `value(value_two) - value(value_one) = weight`.
+    ///
+    /// If the values are already in the same set the union succeeds only if the recorded offset between them equals
+    /// `weight`, and otherwise fails with `WeightedUnionError::Inconsistent`.
+    pub fn union(&mut self, value_one: T, value_two: T, weight: W) -> Result<UnionResult<T>, WeightedUnionError<W>> {
+        let index_one = *self.indices.get(&value_one).ok_or(WeightedUnionError::MissingValue)?;
+        let index_two = *self.indices.get(&value_two).ok_or(WeightedUnionError::MissingValue)?;
+        
+        let (root_one, weight_one) = self.find_root(index_one);
+        let (root_two, weight_two) = self.find_root(index_two);
+        
+        if root_one == root_two {
+            let existing = weight_two.combine(&weight_one.inverse());
+            if existing != weight {
+                return Err(WeightedUnionError::Inconsistent { existing });
+            }
+            return Ok(UnionResult::AlreadyJoined(self.values[root_one].clone()));
+        }
+        
+        // The offset of the second root from the first: value(root_two) - value(root_one).
+        let root_weight = weight.combine(&weight_one).combine(&weight_two.inverse());
+        
+        let root_one_rank = self.ranks[root_one];
+        let root_two_rank = self.ranks[root_two];
+        
+        let (root, child, child_weight) = if root_one_rank < root_two_rank {
+            (root_two, root_one, root_weight.inverse())
+        } else {
+            if root_one_rank == root_two_rank {
+                self.ranks[root_one] = root_one_rank + 1;
+            }
+            (root_one, root_two, root_weight)
+        };
+        
+        self.parents[child] = root;
+        self.weights[child] = child_weight;
+        self.num_sets -= 1;
+        Ok(UnionResult::Merged {
+            root: self.values[root].clone(),
+            absorbed: self.values[child].clone()
+        })
+    }
+    
+    /// Returns `value(value_two) - value(value_one)` and performs path compression on both lookups.
+    ///
+    /// Returns `None` if the values are in different sets or one of them does not exist in the `WeightedDisjointSet`.
+    pub fn diff<Q>(&mut self, value_one: &Q, value_two: &Q) -> Option<W>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index_one = *self.indices.get(value_one)?;
+        let index_two = *self.indices.get(value_two)?;
+        let (root_one, weight_one) = self.find_root(index_one);
+        let (root_two, weight_two) = self.find_root(index_two);
+        if root_one != root_two {
+            return None;
+        }
+        Some(weight_two.combine(&weight_one.inverse()))
+    }
+    
+    /// Checks whether the two values belong to the same set, performing path compression on both lookups.
+    ///
+    /// Returns `None` if one of the values does not exist in the `WeightedDisjointSet`.
+    pub fn same_set<Q>(&mut self, value_one: &Q, value_two: &Q) -> Option<bool>
+        where T: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        let index_one = *self.indices.get(value_one)?;
+        let index_two = *self.indices.get(value_two)?;
+        Some(self.find_root(index_one).0 == self.find_root(index_two).0)
+    }
+    
+    /// Finds the root of `index` and its offset from the root, pointing every node on the path directly at the root.
+    fn find_root(&mut self, index: usize) -> (usize, W) {
+        // Finding the root and the total offset of `index` from it
+        let mut root = index;
+        let mut total = W::identity();
+        while self.parents[root] != root {
+            total = total.combine(&self.weights[root]);
+            root = self.parents[root];
+        }
+        
+        // Path compression on visited nodes, peeling each node's own offset off the remaining total
+        let mut node = index;
+        let mut remaining = total.clone();
+        while node != root {
+            let parent = self.parents[node];
+            let weight = std::mem::replace(&mut self.weights[node], remaining.clone());
+            remaining = remaining.combine(&weight.inverse());
+            self.parents[node] = root;
+            node = parent;
+        }
+        
+        (root, total)
+    }
+}
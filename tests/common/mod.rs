@@ -0,0 +1,20 @@
+#![allow(dead_code)]
+
+/// Deterministic xorshift generator so failures are reproducible.
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    /// Returns the next number below `bound`.
+    pub fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+/// Returns `count` edges between random elements of `0..n`.
+pub fn random_edges(n: usize, count: usize, seed: u64) -> Vec<(usize, usize)> {
+    let mut rng = XorShift(seed);
+    (0..count).map(|_| (rng.next(n), rng.next(n))).collect()
+}
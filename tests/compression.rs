@@ -0,0 +1,76 @@
+use disjoint_set::{Compression, DenseDisjointSet, DisjointSet, FullCompression, NoCompression, PathHalving,
+                   PathSplitting};
+
+mod common;
+
+use common::XorShift;
+
+/// The path 0 -> 1 -> 2 -> 3 -> 4 -> 5, with 5 the root.
+fn chain() -> Vec<usize> {
+    vec![1, 2, 3, 4, 5, 5]
+}
+
+#[test]
+fn full_compression_points_path_at_root() {
+    let mut parents = chain();
+    assert_eq!(FullCompression.find_root(&mut parents, 0), 5);
+    assert_eq!(parents, vec![5, 5, 5, 5, 5, 5]);
+}
+
+#[test]
+fn path_halving_skips_every_other_node() {
+    let mut parents = chain();
+    assert_eq!(PathHalving.find_root(&mut parents, 0), 5);
+    assert_eq!(parents, vec![2, 2, 4, 4, 5, 5]);
+}
+
+#[test]
+fn path_splitting_points_every_node_at_grandparent() {
+    let mut parents = chain();
+    assert_eq!(PathSplitting.find_root(&mut parents, 0), 5);
+    assert_eq!(parents, vec![2, 3, 4, 5, 5, 5]);
+}
+
+#[test]
+fn no_compression_leaves_forest_unchanged() {
+    let mut parents = chain();
+    assert_eq!(NoCompression.find_root(&mut parents, 0), 5);
+    assert_eq!(parents, chain());
+}
+
+fn random_partition<C: Compression>(mut set: DenseDisjointSet<C>) -> Vec<usize> {
+    let n = set.len();
+    let mut rng = XorShift(0x0123_4567_89AB_CDEF);
+    for _ in 0..n {
+        let (a, b) = (rng.next(n), rng.next(n));
+        set.union(a, b);
+    }
+    (0..n).map(|i| set.find(i).unwrap()).collect()
+}
+
+#[test]
+fn strategies_agree_on_partition() {
+    let expected = random_partition(DenseDisjointSet::new(2000));
+    assert_eq!(random_partition(DenseDisjointSet::with_compression(2000, FullCompression)), expected);
+    assert_eq!(random_partition(DenseDisjointSet::with_compression(2000, PathSplitting)), expected);
+    assert_eq!(random_partition(DenseDisjointSet::with_compression(2000, NoCompression)), expected);
+}
+
+#[test]
+fn disjoint_set_with_compression() {
+    let mut set: DisjointSet<u32, (), PathSplitting> = DisjointSet::with_compression(PathSplitting);
+    for i in 0..10 {
+        set.make_set(i);
+    }
+    for i in 1..10 {
+        set.union(i, i - 1);
+    }
+    let root = *set.find(&0).unwrap();
+    for i in 0..10 {
+        assert_eq!(set.find_mut(&i), Some(&root));
+    }
+
+    let mut default: DisjointSet<u32, (), FullCompression> = DisjointSet::default();
+    default.make_set(0);
+    assert_eq!(default.find_mut(&0), Some(&0));
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::thread;
+
+use disjoint_set::{ConcurrentDisjointSet, DisjointSet, UnionResult};
+
+mod common;
+
+use common::{random_edges, XorShift};
+
+/// Asserts that the concurrent set describes exactly the same partition as the sequential one.
+fn assert_same_partition(concurrent: &ConcurrentDisjointSet, sequential: &DisjointSet<usize>, n: usize) {
+    let mut forward = HashMap::new();
+    let mut backward = HashMap::new();
+    for i in 0..n {
+        let concurrent_root = concurrent.find(i).unwrap();
+        let sequential_root = *sequential.find(&i).unwrap();
+        assert_eq!(*forward.entry(concurrent_root).or_insert(sequential_root), sequential_root);
+        assert_eq!(*backward.entry(sequential_root).or_insert(concurrent_root), concurrent_root);
+    }
+}
+
+#[test]
+fn is_send_and_sync() {
+    fn assert_send_sync<S: Send + Sync>() {}
+    assert_send_sync::<ConcurrentDisjointSet>();
+}
+
+#[test]
+fn sequential_behaviour() {
+    let set = ConcurrentDisjointSet::new(4);
+    assert_eq!(set.len(), 4);
+    assert!(ConcurrentDisjointSet::new(0).is_empty());
+    assert_eq!(set.find(4), None);
+    assert_eq!(set.union(0, 4), None);
+    assert_eq!(set.same_set(4, 0), None);
+
+    assert!(set.union(0, 1).unwrap().merged());
+    assert!(set.union(2, 3).unwrap().merged());
+    assert_eq!(set.same_set(0, 1), Some(true));
+    assert_eq!(set.same_set(1, 2), Some(false));
+
+    let root = *set.union(1, 3).unwrap().root();
+    assert_eq!(set.union(0, 2), Some(UnionResult::AlreadyJoined(root)));
+    for i in 0..4 {
+        assert_eq!(set.find(i), Some(root));
+    }
+}
+
+#[test]
+fn stress_random_unions_match_sequential() {
+    const N: usize = 20_000;
+    const THREADS: usize = 8;
+
+    for seed in 1..4u64 {
+        let edges = random_edges(N, N * 3 / 4, seed);
+        let concurrent = ConcurrentDisjointSet::new(N);
+
+        thread::scope(|scope| {
+            for chunk in edges.chunks(edges.len() / THREADS + 1) {
+                let concurrent = &concurrent;
+                scope.spawn(move || {
+                    for &(a, b) in chunk {
+                        concurrent.union(a, b);
+                        concurrent.find(a);
+                    }
+                });
+            }
+        });
+
+        let mut sequential = DisjointSet::new();
+        for i in 0..N {
+            sequential.make_set(i);
+        }
+        for &(a, b) in edges.iter() {
+            sequential.union(a, b);
+        }
+
+        assert_same_partition(&concurrent, &sequential, N);
+    }
+}
+
+#[test]
+fn stress_merges_are_counted_once() {
+    const N: usize = 10_000;
+    const THREADS: usize = 8;
+
+    // Every thread races to union the same chain, so each merge must be reported by exactly one thread.
+    let set = ConcurrentDisjointSet::new(N);
+    let merges: usize = thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let set = &set;
+                scope.spawn(move || {
+                    let mut rng = XorShift(t as u64 + 1);
+                    (1..N)
+                        .map(|_| rng.next(N - 1) + 1)
+                        .chain(1..N)
+                        .filter(|&i| set.union(i - 1, i).unwrap().merged())
+                        .count()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+    });
+
+    assert_eq!(merges, N - 1);
+    let root = set.find(0).unwrap();
+    for i in 0..N {
+        assert_eq!(set.find(i), Some(root));
+        assert_eq!(set.same_set(0, i), Some(true));
+    }
+}
+
+#[test]
+fn stress_same_set_during_unions() {
+    const N: usize = 5_000;
+
+    // Connectivity only ever grows, so once a reader has seen two elements joined it must never see them apart.
+    let set = ConcurrentDisjointSet::new(N);
+    let edges = random_edges(N, N, 42);
+    thread::scope(|scope| {
+        let set = &set;
+        scope.spawn(move || {
+            for &(a, b) in edges.iter() {
+                set.union(a, b);
+            }
+        });
+        for t in 0..4u64 {
+            scope.spawn(move || {
+                let mut rng = XorShift(t + 100);
+                let pairs: Vec<(usize, usize)> = (0..64).map(|_| (rng.next(N), rng.next(N))).collect();
+                let mut joined = vec![false; pairs.len()];
+                for _ in 0..200 {
+                    for (k, &(a, b)) in pairs.iter().enumerate() {
+                        let now = set.same_set(a, b).unwrap();
+                        assert!(now || !joined[k]);
+                        joined[k] = now;
+                    }
+                }
+            });
+        }
+    });
+}
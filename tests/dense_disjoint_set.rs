@@ -0,0 +1,93 @@
+use disjoint_set::{DenseDisjointSet, UnionResult};
+
+#[test]
+fn new_creates_singletons() {
+    let mut set = DenseDisjointSet::new(4);
+    for i in 0..4 {
+        assert_eq!(set.find(i), Some(i));
+    }
+    assert_eq!(set.find(4), None);
+}
+
+#[test]
+fn make_set_appends_singleton() {
+    let mut set = DenseDisjointSet::new(2);
+    assert_eq!(set.make_set(), 2);
+    assert_eq!(set.find(2), Some(2));
+}
+
+#[test]
+fn union_missing_is_none() {
+    let mut set = DenseDisjointSet::new(2);
+    assert_eq!(set.union(0, 2), None);
+    assert_eq!(set.union(2, 0), None);
+}
+
+#[test]
+fn union_matches_disjoint_set_semantics() {
+    let mut set = DenseDisjointSet::new(3);
+    assert_eq!(set.union(0, 1), Some(UnionResult::Merged { root: 0, absorbed: 1 }));
+    assert_eq!(set.union(2, 0), Some(UnionResult::Merged { root: 0, absorbed: 2 }));
+    assert_eq!(set.union(1, 2), Some(UnionResult::AlreadyJoined(0)));
+    for i in 0..3 {
+        assert_eq!(set.find(i), Some(0));
+    }
+}
+
+#[test]
+fn union_is_transitive() {
+    let mut set = DenseDisjointSet::new(1000);
+    for i in (0..1000).step_by(2).skip(1) {
+        set.union(i - 2, i);
+    }
+    let even = set.find(0).unwrap();
+    let odd = set.find(1).unwrap();
+    for i in 0..1000 {
+        let expected = if i % 2 == 0 { even } else { i };
+        assert_eq!(set.find(i), Some(expected));
+    }
+    assert_ne!(even, odd);
+}
+
+#[test]
+fn same_set_reports_connectivity() {
+    let mut set = DenseDisjointSet::new(3);
+    set.union(0, 1);
+    assert_eq!(set.same_set(1, 0), Some(true));
+    assert_eq!(set.same_set(0, 2), Some(false));
+    assert_eq!(set.same_set(0, 3), None);
+}
+
+#[test]
+fn sizes_and_counts() {
+    let mut set = DenseDisjointSet::new(4);
+    assert_eq!(set.len(), 4);
+    assert!(!set.is_empty());
+    assert!(DenseDisjointSet::new(0).is_empty());
+
+    set.union(0, 1);
+    set.union(1, 2);
+    assert_eq!(set.num_sets(), 2);
+    assert_eq!(set.set_size(2), Some(3));
+    assert_eq!(set.set_size(3), Some(1));
+    assert_eq!(set.set_size(4), None);
+
+    set.make_set();
+    assert_eq!(set.len(), 5);
+    assert_eq!(set.num_sets(), 3);
+}
+
+#[test]
+fn members_and_sets() {
+    let mut set = DenseDisjointSet::new(5);
+    set.union(0, 2);
+    set.union(4, 2);
+    let mut members: Vec<usize> = set.members(4).unwrap().collect();
+    members.sort();
+    assert_eq!(members, vec![0, 2, 4]);
+    assert!(set.members(5).is_none());
+
+    let mut sets: Vec<Vec<usize>> = set.sets().map(|mut s| { s.sort(); s }).collect();
+    sets.sort();
+    assert_eq!(sets, vec![vec![0, 2, 4], vec![1], vec![3]]);
+}
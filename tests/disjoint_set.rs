@@ -0,0 +1,584 @@
+use disjoint_set::{DisjointSet, MakeSetResult, Merge, UnionError, UnionResult};
+
+mod common;
+
+use common::XorShift;
+
+fn singletons(n: u32) -> DisjointSet<u32> {
+    let mut set = DisjointSet::new();
+    for i in 0..n {
+        set.make_set(i);
+    }
+    set
+}
+
+#[test]
+fn find_singleton_is_itself() {
+    let set = singletons(3);
+    assert_eq!(set.find(&0), Some(&0));
+    assert_eq!(set.find(&1), Some(&1));
+    assert_eq!(set.find(&2), Some(&2));
+}
+
+#[test]
+fn find_missing_is_none() {
+    let set = singletons(3);
+    assert_eq!(set.find(&7), None);
+}
+
+#[test]
+fn union_missing_is_none() {
+    let mut set = singletons(3);
+    assert_eq!(set.union(0, 7), None);
+    assert_eq!(set.union(7, 0), None);
+    assert_eq!(set.find(&0), Some(&0));
+}
+
+#[test]
+fn union_equal_rank_attaches_second_under_first() {
+    let mut set = singletons(2);
+    assert_eq!(set.union(0, 1), Some(UnionResult::Merged { root: 0, absorbed: 1 }));
+    assert_eq!(set.find(&0), Some(&0));
+    assert_eq!(set.find(&1), Some(&0));
+}
+
+#[test]
+fn union_attaches_lower_rank_under_higher() {
+    let mut set = singletons(3);
+    set.union(0, 1);
+    // {0, 1} has rank 1, {2} has rank 0.
+    assert_eq!(set.union(2, 0), Some(UnionResult::Merged { root: 0, absorbed: 2 }));
+    assert_eq!(set.find(&2), Some(&0));
+    assert_eq!(set.union(1, 2), Some(UnionResult::AlreadyJoined(0)));
+}
+
+#[test]
+fn union_same_set_returns_root() {
+    let mut set = singletons(2);
+    set.union(0, 1);
+    assert_eq!(set.union(1, 0), Some(UnionResult::AlreadyJoined(0)));
+    assert_eq!(set.union(0, 0), Some(UnionResult::AlreadyJoined(0)));
+}
+
+#[test]
+fn union_is_transitive() {
+    let mut set = singletons(6);
+    set.union(0, 1);
+    set.union(2, 3);
+    set.union(1, 3);
+    set.union(4, 5);
+
+    let root = *set.find(&0).unwrap();
+    for i in 1..4 {
+        assert_eq!(set.find(&i), Some(&root));
+    }
+    assert_ne!(set.find(&4), Some(&root));
+    assert_eq!(set.find(&4), set.find(&5));
+}
+
+#[test]
+fn find_compresses_long_chains() {
+    let mut set = singletons(64);
+    for i in 1..64 {
+        set.union(i - 1, i);
+    }
+    let root = *set.find(&0).unwrap();
+    for i in 0..64 {
+        assert_eq!(set.find_mut(&i), Some(&root));
+    }
+}
+
+#[test]
+fn works_with_string_values() {
+    let mut set = DisjointSet::new();
+    set.make_set("a".to_string());
+    set.make_set("b".to_string());
+    set.union("a".to_string(), "b".to_string());
+    assert_eq!(set.find("b"), Some(&"a".to_string()));
+}
+
+#[test]
+fn same_set_reports_connectivity() {
+    let mut set = singletons(4);
+    set.union(0, 1);
+    set.union(1, 2);
+    assert_eq!(set.same_set(&0, &2), Some(true));
+    assert_eq!(set.same_set(&2, &0), Some(true));
+    assert_eq!(set.same_set(&0, &3), Some(false));
+    assert_eq!(set.same_set(&3, &3), Some(true));
+}
+
+#[test]
+fn same_set_missing_is_none() {
+    let set = singletons(2);
+    assert_eq!(set.same_set(&0, &7), None);
+    assert_eq!(set.same_set(&7, &0), None);
+}
+
+#[test]
+fn len_and_is_empty() {
+    let mut set = DisjointSet::new();
+    assert!(set.is_empty());
+    assert_eq!(set.len(), 0);
+    set.make_set('a');
+    set.make_set('b');
+    assert!(!set.is_empty());
+    assert_eq!(set.len(), 2);
+    set.union('a', 'b');
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn num_sets_and_set_size_track_unions() {
+    let mut set = singletons(5);
+    assert_eq!(set.num_sets(), 5);
+    assert_eq!(set.set_size(&0), Some(1));
+
+    set.union(0, 1);
+    set.union(2, 3);
+    set.union(1, 3);
+    assert_eq!(set.num_sets(), 2);
+    for i in 0..4 {
+        assert_eq!(set.set_size(&i), Some(4));
+    }
+    assert_eq!(set.set_size(&4), Some(1));
+
+    // Unioning values already in the same set changes nothing.
+    set.union(0, 3);
+    assert_eq!(set.num_sets(), 2);
+    assert_eq!(set.set_size(&0), Some(4));
+}
+
+#[test]
+fn set_size_missing_is_none() {
+    let set = singletons(1);
+    assert_eq!(set.set_size(&7), None);
+}
+
+fn sorted<'a, I: Iterator<Item = &'a u32>>(iter: I) -> Vec<u32> {
+    let mut values: Vec<u32> = iter.cloned().collect();
+    values.sort();
+    values
+}
+
+#[test]
+fn members_lists_the_whole_set() {
+    let mut set = singletons(6);
+    set.union(0, 1);
+    set.union(2, 3);
+    set.union(3, 1);
+    assert_eq!(sorted(set.members(&2).unwrap()), vec![0, 1, 2, 3]);
+    assert_eq!(set.members(&3).unwrap().next(), Some(&3));
+    assert_eq!(sorted(set.members(&5).unwrap()), vec![5]);
+    assert!(set.members(&9).is_none());
+}
+
+#[test]
+fn sets_lists_every_partition() {
+    let mut set = singletons(5);
+    set.union(0, 4);
+    set.union(1, 3);
+    let mut sets: Vec<Vec<u32>> = set.sets().map(|s| sorted(s.into_iter())).collect();
+    sets.sort();
+    assert_eq!(sets, vec![vec![0, 4], vec![1, 3], vec![2]]);
+    assert_eq!(set.sets().count(), set.num_sets());
+}
+
+#[test]
+fn union_result_accessors() {
+    let mut set = singletons(3);
+    let merged = set.union(2, 1).unwrap();
+    assert!(merged.merged());
+    assert_eq!(merged.root(), &2);
+
+    let joined = set.union(1, 2).unwrap();
+    assert!(!joined.merged());
+    assert_eq!(joined.root(), &2);
+    assert_eq!(joined.map(|root| root * 10), UnionResult::AlreadyJoined(20));
+}
+
+#[test]
+fn make_set_leaves_existing_values_untouched() {
+    let mut set = singletons(3);
+    set.union(0, 1);
+    set.union(1, 2);
+    assert!(!set.make_set(1));
+    assert!(!set.make_set(0));
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.num_sets(), 1);
+    for i in 0..3 {
+        assert_eq!(set.find(&i), Some(&0));
+    }
+    assert!(set.make_set(3));
+    assert_eq!(set.len(), 4);
+}
+
+#[test]
+fn make_set_or_get_reports_which_case_happened() {
+    let mut set = singletons(2);
+    set.union(1, 0);
+    assert_eq!(set.make_set_or_get(0), MakeSetResult::Existing(1));
+    assert_eq!(set.make_set_or_get(2), MakeSetResult::Created);
+    assert_eq!(set.make_set_or_get(2), MakeSetResult::Existing(2));
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn collect_and_extend_make_sets() {
+    let mut set: DisjointSet<u32> = vec![0, 1, 2, 1].into_iter().collect();
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.num_sets(), 3);
+    set.union(0, 1);
+    set.extend(vec![1, 3, 4]);
+    assert_eq!(set.len(), 5);
+    assert_eq!(set.find(&1), Some(&0));
+    assert_eq!(set.find(&4), Some(&4));
+}
+
+#[test]
+fn from_edges_creates_and_unions_values() {
+    let set = DisjointSet::from_edges(vec![(0, 1), (2, 3), (1, 3), (4, 4), (5, 6)]);
+    assert_eq!(set.len(), 7);
+    assert_eq!(set.num_sets(), 3);
+    assert_eq!(sorted(set.members(&3).unwrap()), vec![0, 1, 2, 3]);
+    assert_eq!(set.find(&4), Some(&4));
+    assert_eq!(set.find(&6), set.find(&5));
+}
+
+#[test]
+fn union_insert_creates_missing_values() {
+    let mut set = singletons(2);
+    assert_eq!(set.union_insert(0, 5), UnionResult::Merged { root: 0, absorbed: 5 });
+    assert_eq!(set.union_insert(7, 8), UnionResult::Merged { root: 7, absorbed: 8 });
+    assert_eq!(set.union_insert(8, 5), UnionResult::Merged { root: 7, absorbed: 0 });
+    assert_eq!(set.union_insert(9, 9), UnionResult::AlreadyJoined(9));
+    assert_eq!(set.len(), 6);
+    assert_eq!(set.num_sets(), 3);
+    assert_eq!(sorted(set.members(&0).unwrap()), vec![0, 5, 7, 8]);
+}
+
+#[test]
+fn try_union_names_missing_values() {
+    let mut set = singletons(2);
+    assert_eq!(set.try_union(7, 0), Err(UnionError::MissingFirst));
+    assert_eq!(set.try_union(0, 7), Err(UnionError::MissingSecond));
+    assert_eq!(set.try_union(7, 8), Err(UnionError::MissingBoth));
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.try_union(0, 1), Ok(UnionResult::Merged { root: 0, absorbed: 1 }));
+    assert_eq!(set.try_union(1, 0), Ok(UnionResult::AlreadyJoined(0)));
+}
+
+#[test]
+fn clone_is_independent_of_original() {
+    let mut original = singletons(4);
+    original.union(0, 1);
+
+    let mut copy = original.clone();
+    assert_eq!(copy.find(&1), Some(&0));
+    assert_eq!(copy.num_sets(), 3);
+
+    copy.union(2, 3);
+    copy.union(0, 2);
+    assert_eq!(copy.same_set(&1, &3), Some(true));
+    assert_eq!(copy.num_sets(), 1);
+    assert_eq!(original.same_set(&1, &3), Some(false));
+    assert_eq!(original.same_set(&2, &3), Some(false));
+    assert_eq!(original.num_sets(), 3);
+
+    original.union(1, 3);
+    assert_eq!(original.set_size(&3), Some(3));
+    assert_eq!(copy.set_size(&3), Some(4));
+    assert_eq!(copy.same_set(&0, &2), Some(true));
+}
+
+#[test]
+fn find_works_through_shared_references() {
+    let mut set = singletons(4);
+    set.union(0, 1);
+    set.union(1, 2);
+    let shared = &set;
+    assert_eq!(shared.find(&2), Some(&0));
+    assert_eq!(shared.find(&3), Some(&3));
+    assert_eq!(shared.find(&7), None);
+}
+
+#[test]
+fn find_mut_compresses_and_agrees_with_find() {
+    let mut set = singletons(32);
+    for i in 1..32 {
+        set.union(i, i - 1);
+    }
+    let root = *set.find(&31).unwrap();
+    for i in 0..32 {
+        assert_eq!(set.find_mut(&i), Some(&root));
+        assert_eq!(set.find(&i), Some(&root));
+    }
+    assert_eq!(set.find_mut(&32), None);
+}
+
+#[test]
+fn queries_work_through_shared_references() {
+    let mut set = singletons(4);
+    set.union(0, 1);
+    set.union(2, 1);
+    let shared = &set;
+    assert_eq!(shared.same_set(&0, &2), Some(true));
+    assert_eq!(shared.same_set(&0, &3), Some(false));
+    assert_eq!(shared.same_set(&0, &9), None);
+    assert_eq!(shared.set_size(&2), Some(3));
+    assert_eq!(shared.set_size(&9), None);
+    assert_eq!(set.same_set_mut(&2, &0), Some(true));
+    assert_eq!(set.same_set_mut(&3, &9), None);
+}
+
+#[test]
+fn lookups_accept_borrowed_keys() {
+    let mut set = DisjointSet::new();
+    for word in ["apple", "banana", "cherry"].iter() {
+        set.make_set(word.to_string());
+    }
+    set.union("apple".to_string(), "cherry".to_string());
+    assert_eq!(set.find("cherry"), Some(&"apple".to_string()));
+    assert_eq!(set.find_mut("cherry"), Some(&"apple".to_string()));
+    assert_eq!(set.same_set("apple", "cherry"), Some(true));
+    assert_eq!(set.same_set("apple", "banana"), Some(false));
+    assert_eq!(set.set_size("apple"), Some(2));
+    assert_eq!(set.members("banana").unwrap().count(), 1);
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Stats {
+    sum: i64,
+    min: i64,
+    max: i64
+}
+
+impl Stats {
+    fn of(value: i64) -> Stats {
+        Stats { sum: value, min: value, max: value }
+    }
+}
+
+impl Merge for Stats {
+    fn merge(&mut self, absorbed: Stats) {
+        self.sum += absorbed.sum;
+        self.min = self.min.min(absorbed.min);
+        self.max = self.max.max(absorbed.max);
+    }
+}
+
+#[test]
+fn set_data_is_merged_on_union() {
+    let mut set: DisjointSet<i64, Stats> = DisjointSet::default();
+    for i in 1..=5 {
+        set.make_set_with(i, Stats::of(i * 10));
+    }
+    set.union(1, 2);
+    set.union(4, 5);
+    set.union(5, 2);
+
+    let expected = Stats { sum: 120, min: 10, max: 50 };
+    for i in [1, 2, 4, 5].iter() {
+        assert_eq!(set.set_data(i), Some(&expected));
+    }
+    assert_eq!(set.set_data(&3), Some(&Stats::of(30)));
+    assert_eq!(set.set_data(&6), None);
+
+    // Unioning values already in the same set does not merge the data again.
+    set.union(1, 5);
+    assert_eq!(set.set_data(&1), Some(&expected));
+}
+
+#[test]
+fn set_data_mut_updates_whole_set() {
+    let mut set: DisjointSet<u32, Vec<u32>> = DisjointSet::default();
+    set.make_set(0);
+    set.make_set(1);
+    set.union_with(0, 1, |root, absorbed| root.extend(absorbed));
+    set.set_data_mut(&1).unwrap().push(7);
+    assert_eq!(set.set_data(&0), Some(&vec![7]));
+    assert_eq!(set.set_data_mut(&2), None);
+}
+
+#[test]
+fn union_with_passes_surviving_data_first() {
+    let mut set: DisjointSet<char, String> = DisjointSet::default();
+    set.make_set_with('a', "a".to_string());
+    set.make_set_with('b', "b".to_string());
+    set.make_set_with('c', "c".to_string());
+    let result = set.union_with('b', 'a', |root, absorbed| root.push_str(&absorbed)).unwrap();
+    assert_eq!(result, UnionResult::Merged { root: 'b', absorbed: 'a' });
+    assert_eq!(set.set_data(&'a'), Some(&"ba".to_string()));
+    assert!(!set.make_set_with('a', "ignored".to_string()));
+    assert_eq!(set.set_data(&'a'), Some(&"ba".to_string()));
+    assert_eq!(set.union_with('c', 'd', |_, _| panic!("missing value merged")), None);
+}
+
+#[test]
+fn remove_keeps_rest_of_set_together() {
+    let mut set = singletons(5);
+    set.union(0, 1);
+    set.union(1, 2);
+    set.union(3, 4);
+
+    assert_eq!(set.remove(&1), Some(1));
+    assert_eq!(set.remove(&1), None);
+    assert_eq!(set.find(&1), None);
+    assert_eq!(set.len(), 4);
+    assert_eq!(set.num_sets(), 2);
+    assert_eq!(set.same_set(&0, &2), Some(true));
+    assert_eq!(set.set_size(&2), Some(2));
+    assert_eq!(sorted(set.members(&0).unwrap()), vec![0, 2]);
+}
+
+#[test]
+fn remove_root_keeps_rest_of_set_together() {
+    let mut set = singletons(4);
+    set.union(0, 1);
+    set.union(0, 2);
+    set.union(0, 3);
+    assert_eq!(set.find(&3), Some(&0));
+
+    assert_eq!(set.remove(&0), Some(0));
+    let root = *set.find(&1).unwrap();
+    assert_ne!(root, 0);
+    for i in 1..4 {
+        assert_eq!(set.find(&i), Some(&root));
+        assert_eq!(set.find_mut(&i), Some(&root));
+    }
+    assert_eq!(set.set_size(&3), Some(3));
+    assert_eq!(set.num_sets(), 1);
+}
+
+#[test]
+fn remove_last_value_drops_set() {
+    let mut set = singletons(3);
+    set.union(0, 1);
+    set.remove(&2);
+    assert_eq!(set.num_sets(), 1);
+    assert_eq!(set.sets().count(), 1);
+    set.remove(&1);
+    set.remove(&0);
+    assert!(set.is_empty());
+    assert_eq!(set.num_sets(), 0);
+    assert_eq!(set.sets().count(), 0);
+
+    assert!(set.make_set(1));
+    assert_eq!(set.find(&1), Some(&1));
+    assert_eq!(set.num_sets(), 1);
+}
+
+#[test]
+fn remove_keeps_set_data() {
+    let mut set: DisjointSet<i64, Stats> = DisjointSet::default();
+    for i in 0..4 {
+        set.make_set_with(i, Stats::of(i));
+    }
+    set.union(0, 1);
+    set.union(0, 2);
+    set.remove(&0);
+    set.remove(&3);
+    assert_eq!(set.set_data(&2), Some(&Stats { sum: 3, min: 0, max: 2 }));
+    assert_eq!(set.set_data(&3), None);
+}
+
+#[test]
+fn isolate_moves_value_to_singleton() {
+    let mut set = singletons(4);
+    set.union(0, 1);
+    set.union(1, 2);
+
+    assert!(set.isolate(&0));
+    assert_eq!(set.same_set(&0, &1), Some(false));
+    assert_eq!(set.same_set(&1, &2), Some(true));
+    assert_eq!(set.find(&0), Some(&0));
+    assert_eq!(set.set_size(&0), Some(1));
+    assert_eq!(set.set_size(&2), Some(2));
+    assert_eq!(set.num_sets(), 3);
+    assert_eq!(set.len(), 4);
+
+    assert!(set.isolate(&3));
+    assert_eq!(set.num_sets(), 3);
+    assert!(!set.isolate(&9));
+}
+
+#[test]
+fn isolate_resets_data_of_singleton() {
+    let mut set: DisjointSet<u32, Stats> = DisjointSet::default();
+    set.make_set_with(0, Stats::of(7));
+    set.make_set_with(1, Stats::of(3));
+    set.union(0, 1);
+
+    assert!(set.isolate(&1));
+    assert_eq!(set.set_data(&1), Some(&Stats::default()));
+    set.set_data_mut(&1).unwrap().sum = 5;
+    assert!(set.isolate(&1));
+    assert_eq!(set.set_data(&1), Some(&Stats::default()));
+    assert_eq!(set.set_data(&0).unwrap().sum, 10);
+}
+
+#[test]
+fn removals_match_brute_force_model() {
+    const N: u32 = 300;
+    let mut rng = XorShift(0xDEAD_BEEF_CAFE_F00D);
+    let mut next = move |bound: u32| rng.next(bound as usize) as u32;
+
+    // The model keeps an explicit label per present value; labels are merged by relabelling.
+    let mut labels: Vec<Option<u32>> = (0..N).map(Some).collect();
+    let mut fresh = N;
+    let mut set = singletons(N);
+
+    for step in 0..5000 {
+        let (a, b) = (next(N), next(N));
+        match next(4) {
+            0 | 1 => {
+                set.union(a, b);
+                if let (Some(la), Some(lb)) = (labels[a as usize], labels[b as usize]) {
+                    for label in labels.iter_mut() {
+                        if *label == Some(lb) {
+                            *label = Some(la);
+                        }
+                    }
+                }
+            }
+            2 => {
+                assert_eq!(set.remove(&a).is_some(), labels[a as usize].is_some());
+                labels[a as usize] = None;
+            }
+            _ => {
+                if labels[a as usize].is_some() {
+                    assert!(set.isolate(&a));
+                    labels[a as usize] = Some(fresh);
+                } else {
+                    assert!(set.make_set(a));
+                    labels[a as usize] = Some(fresh);
+                }
+                fresh += 1;
+            }
+        }
+
+        if step % 50 == 0 {
+            let present = labels.iter().filter(|label| label.is_some()).count();
+            assert_eq!(set.len(), present);
+            let mut distinct: Vec<u32> = labels.iter().filter_map(|label| *label).collect();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(set.num_sets(), distinct.len());
+            assert_eq!(set.sets().count(), distinct.len());
+            for x in 0..N {
+                for y in (0..N).step_by(17) {
+                    let expected = match (labels[x as usize], labels[y as usize]) {
+                        (Some(lx), Some(ly)) => Some(lx == ly),
+                        _ => None
+                    };
+                    assert_eq!(set.same_set(&x, &y), expected);
+                }
+                if let Some(lx) = labels[x as usize] {
+                    let size = labels.iter().filter(|label| **label == Some(lx)).count();
+                    assert_eq!(set.set_size(&x), Some(size));
+                    assert_eq!(set.members(&x).unwrap().count(), size);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use disjoint_set::{DisjointSet, DotStyle};
+
+fn joined() -> DisjointSet<u32> {
+    let mut set = DisjointSet::new();
+    for i in 0..4 {
+        set.make_set(i);
+    }
+    set.union(0, 1);
+    set.union(2, 0);
+    set
+}
+
+#[test]
+fn forest_shows_ranks_and_parent_edges() {
+    let dot = joined().to_dot(DotStyle::Forest);
+    assert!(dot.starts_with("digraph disjoint_set {\n"));
+    assert!(dot.contains("n0 [label=\"0\\nrank 1\", peripheries=2];"));
+    assert!(dot.contains("n1 [label=\"1\\nrank 0\"];"));
+    assert!(dot.contains("n1 -> n0;"));
+    assert!(dot.contains("n2 -> n0;"));
+    assert!(!dot.contains("n3 ->"));
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn forest_shows_vacant_nodes() {
+    let mut set = joined();
+    set.make_set(4);
+    set.make_set(5);
+    set.remove(&1);
+    let dot = set.to_dot(DotStyle::Forest);
+    assert!(dot.contains("n1 [label=\"(vacant)\\nrank 0\", style=dashed];"));
+    assert!(dot.contains("n1 -> n0;"));
+}
+
+#[test]
+fn partition_has_one_cluster_per_set() {
+    let dot = joined().to_dot(DotStyle::Partition);
+    assert!(dot.starts_with("graph disjoint_set {\n"));
+    assert_eq!(dot.matches("subgraph cluster_").count(), 2);
+    assert!(dot.contains("label=\"size 3\";"));
+    assert!(dot.contains("label=\"size 1\";"));
+    assert!(!dot.contains("--"));
+}
+
+#[test]
+fn labels_are_escaped() {
+    let mut set = DisjointSet::new();
+    set.make_set("say \"hi\"");
+    let dot = set.to_dot(DotStyle::Partition);
+    assert!(dot.contains(r#"[label="\"say \\\"hi\\\"\"", peripheries=2];"#));
+}
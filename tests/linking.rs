@@ -0,0 +1,184 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use disjoint_set::{ByMinKey, ByRandomPriority, ByRank, BySize, DenseDisjointSet, DisjointSet, FullCompression,
+                   Linking, RootInfo, UnionResult};
+
+mod common;
+
+use common::{random_edges, XorShift};
+
+#[test]
+fn by_rank_is_the_default() {
+    let mut set: DisjointSet<u32, (), _, ByRank> = DisjointSet::with_linking(ByRank);
+    for i in 0..3 {
+        set.make_set(i);
+    }
+    assert_eq!(set.union(0, 1), Some(UnionResult::Merged { root: 0, absorbed: 1 }));
+    assert_eq!(set.union(2, 1), Some(UnionResult::Merged { root: 0, absorbed: 2 }));
+}
+
+#[test]
+fn by_size_keeps_larger_set() {
+    let mut set = DenseDisjointSet::with_linking(6, BySize);
+    set.union(0, 1);
+    set.union(0, 2);
+    set.union(3, 4);
+    // {3, 4} is smaller than {0, 1, 2}, so it goes under 0 even though it is passed first.
+    assert_eq!(set.union(3, 0), Some(UnionResult::Merged { root: 0, absorbed: 3 }));
+    assert_eq!(set.union(5, 4), Some(UnionResult::Merged { root: 0, absorbed: 5 }));
+}
+
+#[test]
+fn by_min_key_gives_canonical_representatives() {
+    const N: usize = 500;
+    let edges = random_edges(N, N / 2, 7);
+
+    let mut expected = None;
+    for seed in 1..6u64 {
+        let mut order = edges.clone();
+        let mut rng = XorShift(seed);
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.next(i + 1));
+        }
+
+        let mut set = DisjointSet::with_linking(ByMinKey);
+        for i in (0..N).rev() {
+            set.make_set(i);
+        }
+        for &(a, b) in order.iter() {
+            set.union(a, b);
+        }
+
+        let roots: Vec<usize> = (0..N).map(|i| *set.find(&i).unwrap()).collect();
+        for (i, root) in roots.iter().enumerate() {
+            assert_eq!(root, set.members(&i).unwrap().min().unwrap());
+        }
+        match expected {
+            None => expected = Some(roots),
+            Some(ref expected) => assert_eq!(&roots, expected)
+        }
+    }
+}
+
+#[test]
+fn by_min_key_survives_removing_the_minimum() {
+    let orders = [[(0, 3), (0, 5), (0, 9)], [(9, 5), (3, 9), (0, 5)], [(5, 0), (9, 3), (3, 0)], [(3, 5), (9, 0), (5, 9)]];
+    for order in orders.iter() {
+        let mut set = DisjointSet::with_linking(ByMinKey);
+        for &i in [9, 5, 3, 0].iter() {
+            set.make_set(i);
+        }
+        for &(a, b) in order.iter() {
+            set.union(a, b);
+        }
+        assert_eq!(set.remove(&0), Some(0));
+        for &i in [3, 5, 9].iter() {
+            assert_eq!(set.find(&i), Some(&3));
+        }
+        assert_eq!(set.remove(&3), Some(3));
+        assert_eq!(set.find(&9), Some(&5));
+    }
+}
+
+#[test]
+fn by_min_key_on_dense_elements() {
+    let mut set = DenseDisjointSet::with_linking(4, ByMinKey);
+    set.union(3, 2);
+    set.union(2, 1);
+    assert_eq!(set.find(3), Some(1));
+}
+
+/// Labels every element by the order in which its set is first seen, so partitions compare equal whatever the roots.
+fn canonical_partition<L: Linking<usize>>(mut set: DenseDisjointSet<FullCompression, L>, edges: &[(usize, usize)]) -> Vec<usize> {
+    for &(a, b) in edges {
+        set.union(a, b);
+    }
+    let mut labels = vec![usize::MAX; set.len()];
+    let mut canonical = Vec::new();
+    for i in 0..set.len() {
+        let root = set.find(i).unwrap();
+        if labels[root] == usize::MAX {
+            labels[root] = canonical.len();
+        }
+        canonical.push(labels[root]);
+    }
+    canonical
+}
+
+#[test]
+fn policies_agree_on_partition() {
+    const N: usize = 2000;
+    let edges = random_edges(N, N, 3);
+    let by_rank = canonical_partition(DenseDisjointSet::with_strategies(N, FullCompression, ByRank), &edges);
+    let by_size = canonical_partition(DenseDisjointSet::with_strategies(N, FullCompression, BySize), &edges);
+    let by_min_key = canonical_partition(DenseDisjointSet::with_strategies(N, FullCompression, ByMinKey), &edges);
+    let by_priority = canonical_partition(DenseDisjointSet::with_strategies(N, FullCompression, ByRandomPriority::new(9)), &edges);
+    assert_eq!(by_size, by_rank);
+    assert_eq!(by_min_key, by_rank);
+    assert_eq!(by_priority, by_rank);
+}
+
+#[test]
+fn strategies_with_set_data() {
+    let mut set: DisjointSet<u32, u32, FullCompression, ByMinKey> = DisjointSet::default();
+    for i in 0..4 {
+        set.make_set_with(i, 1);
+    }
+    set.union_with(3, 2, |root, absorbed| *root += absorbed);
+    set.union_with(2, 1, |root, absorbed| *root += absorbed);
+    assert_eq!(set.find(&3), Some(&1));
+    assert_eq!(set.set_data(&3), Some(&3));
+}
+
+/// A policy defined outside the crate: the larger key wins.
+struct ByMaxKey;
+
+impl<K: Ord + ?Sized> Linking<K> for ByMaxKey {
+    fn keeps_first(&self, first: RootInfo<K>, second: RootInfo<K>) -> bool {
+        first.key >= second.key
+    }
+    
+    fn chooses_by_key(&self) -> bool {
+        true
+    }
+}
+
+/// Union by rank that counts how often it is consulted.
+struct CountingByRank(Rc<Cell<usize>>);
+
+impl<K: ?Sized> Linking<K> for CountingByRank {
+    fn keeps_first(&self, first: RootInfo<K>, second: RootInfo<K>) -> bool {
+        self.0.set(self.0.get() + 1);
+        ByRank.keeps_first(first, second)
+    }
+}
+
+#[test]
+fn removing_root_only_consults_policies_that_choose_by_key() {
+    let calls = Rc::new(Cell::new(0));
+    let mut set = DisjointSet::with_linking(CountingByRank(calls.clone()));
+    for i in 0..100 {
+        set.make_set(i);
+        set.union(0, i);
+    }
+    let consulted = calls.get();
+    assert_eq!(set.remove(&0), Some(0));
+    assert_eq!(calls.get(), consulted);
+    assert_eq!(set.set_size(&50), Some(99));
+}
+
+#[test]
+fn custom_policy() {
+    let mut set = DisjointSet::with_linking(ByMaxKey);
+    for word in ["pear", "apple", "zucchini", "fig"].iter() {
+        set.make_set(word.to_string());
+    }
+    set.union("apple".to_string(), "pear".to_string());
+    set.union("fig".to_string(), "apple".to_string());
+    assert_eq!(set.find("fig"), Some(&"pear".to_string()));
+    set.union("zucchini".to_string(), "fig".to_string());
+    assert_eq!(set.find("apple"), Some(&"zucchini".to_string()));
+    set.remove("zucchini");
+    assert_eq!(set.find("apple"), Some(&"pear".to_string()));
+}
@@ -0,0 +1,76 @@
+use disjoint_set::{Group, Parity, ParityDisjointSet, UnionResult, WeightedUnionError};
+
+fn singletons(n: u32) -> ParityDisjointSet<u32> {
+    let mut set = ParityDisjointSet::new();
+    for i in 0..n {
+        set.make_set(i);
+    }
+    set
+}
+
+#[test]
+fn parity_group_laws() {
+    assert_eq!(Parity::identity(), Parity::Same);
+    assert_eq!(Parity::Different.combine(&Parity::Different), Parity::Same);
+    assert_eq!(Parity::Different.combine(&Parity::Same), Parity::Different);
+    assert_eq!(Parity::Different.inverse(), Parity::Different);
+}
+
+#[test]
+fn relations_propagate() {
+    let mut set = singletons(5);
+    assert_eq!(set.union_different(0, 1), Ok(UnionResult::Merged { root: 0, absorbed: 1 }));
+    set.union_different(1, 2).unwrap();
+    set.union_same(2, 3).unwrap();
+
+    assert_eq!(set.relation(&0, &1), Some(Parity::Different));
+    assert_eq!(set.relation(&0, &2), Some(Parity::Same));
+    assert_eq!(set.relation(&3, &1), Some(Parity::Different));
+    assert_eq!(set.relation(&4, &4), Some(Parity::Same));
+    assert_eq!(set.relation(&0, &4), None);
+    assert_eq!(set.relation(&0, &9), None);
+    assert_eq!(set.same_set(&0, &3), Some(true));
+    assert_eq!(set.num_sets(), 2);
+}
+
+#[test]
+fn odd_cycle_is_reported_when_closed() {
+    let mut set = singletons(3);
+    set.union_different(0, 1).unwrap();
+    set.union_different(1, 2).unwrap();
+    assert_eq!(set.union_different(2, 0), Err(WeightedUnionError::Inconsistent { existing: Parity::Same }));
+    assert_eq!(set.union_same(2, 0), Ok(UnionResult::AlreadyJoined(0)));
+    assert_eq!(set.relation(&0, &2), Some(Parity::Same));
+}
+
+#[test]
+fn missing_value_is_reported() {
+    let mut set = singletons(1);
+    assert_eq!(set.union_same(0, 1), Err(WeightedUnionError::MissingValue));
+    assert_eq!(set.union(1, 0, Parity::Different), Err(WeightedUnionError::MissingValue));
+}
+
+/// Checks bipartiteness of a cycle graph, which is bipartite exactly when its length is even.
+fn cycle_is_bipartite(n: u32) -> bool {
+    let mut set = singletons(n);
+    (0..n).all(|i| set.union_different(i, (i + 1) % n).is_ok())
+}
+
+#[test]
+fn detects_bipartite_cycles() {
+    for n in 2..40 {
+        assert_eq!(cycle_is_bipartite(n), n % 2 == 0, "cycle of length {}", n);
+    }
+}
+
+#[test]
+fn find_and_len() {
+    let mut set = singletons(2);
+    assert!(set.make_set(2));
+    assert!(!set.make_set(2));
+    assert_eq!(set.len(), 3);
+    assert!(!set.is_empty());
+    set.union_same(2, 1).unwrap();
+    assert_eq!(set.find(&1), Some(&2));
+    assert_eq!(set.find_mut(&1), Some(&2));
+}
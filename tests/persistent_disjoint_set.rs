@@ -0,0 +1,116 @@
+use std::hash::{Hash, Hasher};
+
+use disjoint_set::{DisjointSet, PersistentDisjointSet, UnionResult};
+
+mod common;
+
+use common::XorShift;
+
+fn singletons(n: u32) -> PersistentDisjointSet<u32> {
+    (0..n).fold(PersistentDisjointSet::new(), |set, i| set.make_set(i))
+}
+
+#[test]
+fn make_set_leaves_old_version_untouched() {
+    let empty = PersistentDisjointSet::new();
+    let one = empty.make_set('a');
+    let two = one.make_set('b');
+    assert!(empty.is_empty());
+    assert_eq!(one.len(), 1);
+    assert_eq!(two.len(), 2);
+    assert_eq!(one.find(&'b'), None);
+    assert_eq!(two.find(&'b'), Some(&'b'));
+    assert_eq!(two.make_set('a').len(), 2);
+}
+
+#[test]
+fn union_returns_new_version() {
+    let base = singletons(3);
+    let (joined, result) = base.union(&0, &1).unwrap();
+    assert_eq!(result, UnionResult::Merged { root: 0, absorbed: 1 });
+    assert_eq!(joined.same_set(&0, &1), Some(true));
+    assert_eq!(base.same_set(&0, &1), Some(false));
+    assert_eq!(joined.num_sets(), 2);
+    assert_eq!(base.num_sets(), 3);
+    assert_eq!(joined.set_size(&1), Some(2));
+    assert_eq!(base.set_size(&1), Some(1));
+
+    let (again, result) = joined.union(&1, &0).unwrap();
+    assert_eq!(result, UnionResult::AlreadyJoined(0));
+    assert_eq!(again.num_sets(), 2);
+
+    assert!(base.union(&0, &7).is_none());
+    assert_eq!(base.same_set(&0, &7), None);
+}
+
+#[test]
+fn branching_versions_stay_independent() {
+    let base = singletons(4);
+    let (left, _) = base.union(&0, &1).unwrap();
+    let (right, _) = base.union(&2, &3).unwrap();
+    let (left, _) = left.union(&1, &2).unwrap();
+    let right = right.make_set(4);
+
+    assert_eq!(left.same_set(&0, &2), Some(true));
+    assert_eq!(left.same_set(&2, &3), Some(false));
+    assert_eq!(left.find(&4), None);
+    assert_eq!(right.same_set(&0, &1), Some(false));
+    assert_eq!(right.same_set(&2, &3), Some(true));
+    assert_eq!(right.find(&4), Some(&4));
+    assert_eq!(base.num_sets(), 4);
+}
+
+#[test]
+fn every_version_matches_sequential_history() {
+    const N: u32 = 500;
+    let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+    let mut next = move || rng.next(N as usize) as u32;
+
+    let mut versions = vec![singletons(N)];
+    let mut snapshots = Vec::new();
+    let mut sequential = DisjointSet::new();
+    for i in 0..N {
+        sequential.make_set(i);
+    }
+    for _ in 0..400 {
+        let (a, b) = (next(), next());
+        let (version, result) = versions.last().unwrap().union(&a, &b).unwrap();
+        assert_eq!(Some(result), sequential.union(a, b));
+        versions.push(version);
+        snapshots.push(sequential.clone());
+    }
+
+    for (version, snapshot) in versions.iter().skip(1).zip(snapshots.iter()) {
+        assert_eq!(version.num_sets(), snapshot.num_sets());
+        for i in 0..N {
+            assert_eq!(version.find(&i), snapshot.find(&i));
+        }
+    }
+}
+
+/// A value whose hashes all collide, to exercise the trie's collision buckets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Colliding(u32);
+
+impl Hash for Colliding {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.0 % 3).hash(state);
+    }
+}
+
+#[test]
+fn hash_collisions() {
+    let set = (0..30).fold(PersistentDisjointSet::new(), |set, i| set.make_set(Colliding(i)));
+    assert_eq!(set.len(), 30);
+    let set = (1..30).fold(set, |set, i| set.union(&Colliding(i - 1), &Colliding(i)).unwrap().0);
+    assert_eq!(set.num_sets(), 1);
+    assert_eq!(set.set_size(&Colliding(17)), Some(30));
+    assert_eq!(set.find(&Colliding(30)), None);
+}
+
+#[test]
+fn lookups_accept_borrowed_keys() {
+    let set = PersistentDisjointSet::new().make_set("x".to_string()).make_set("y".to_string());
+    let (set, _) = set.union("y", "x").unwrap();
+    assert_eq!(set.find("x"), Some(&"y".to_string()));
+}
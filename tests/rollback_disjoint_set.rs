@@ -0,0 +1,135 @@
+use disjoint_set::{RollbackDisjointSet, UnionResult};
+
+fn singletons(n: u32) -> RollbackDisjointSet<u32> {
+    let mut set = RollbackDisjointSet::new();
+    for i in 0..n {
+        set.make_set(i);
+    }
+    set
+}
+
+fn roots(set: &RollbackDisjointSet<u32>, n: u32) -> Vec<u32> {
+    (0..n).map(|i| *set.find(&i).unwrap()).collect()
+}
+
+#[test]
+fn behaves_like_disjoint_set() {
+    let mut set = singletons(4);
+    assert!(!set.make_set(0));
+    assert_eq!(set.union(0, 1), Some(UnionResult::Merged { root: 0, absorbed: 1 }));
+    assert_eq!(set.union(2, 0), Some(UnionResult::Merged { root: 0, absorbed: 2 }));
+    assert_eq!(set.union(1, 2), Some(UnionResult::AlreadyJoined(0)));
+    assert_eq!(set.union(1, 7), None);
+    assert_eq!(set.same_set(&2, &1), Some(true));
+    assert_eq!(set.same_set(&2, &3), Some(false));
+    assert_eq!(set.set_size(&1), Some(3));
+    assert_eq!(set.num_sets(), 2);
+    assert_eq!(set.len(), 4);
+}
+
+#[test]
+fn rollback_undoes_unions() {
+    let mut set = singletons(6);
+    set.union(0, 1);
+    let before = roots(&set, 6);
+    let checkpoint = set.checkpoint();
+
+    set.union(2, 3);
+    set.union(1, 3);
+    set.union(4, 5);
+    assert_eq!(set.num_sets(), 2);
+
+    set.rollback(checkpoint);
+    assert_eq!(roots(&set, 6), before);
+    assert_eq!(set.num_sets(), 5);
+    assert_eq!(set.set_size(&0), Some(2));
+    assert_eq!(set.set_size(&3), Some(1));
+}
+
+#[test]
+fn rollback_restores_ranks() {
+    let mut set = singletons(3);
+    let checkpoint = set.checkpoint();
+    set.union(1, 2);
+    set.rollback(checkpoint);
+
+    // With rank 0 restored on 1, the tie-break attaches 1 under 0 again.
+    assert_eq!(set.union(0, 1), Some(UnionResult::Merged { root: 0, absorbed: 1 }));
+}
+
+#[test]
+fn rollback_removes_values_made_after_checkpoint() {
+    let mut set = singletons(2);
+    let checkpoint = set.checkpoint();
+    assert!(set.make_set(2));
+    set.union(2, 0);
+    set.rollback(checkpoint);
+
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.find(&2), None);
+    assert_eq!(set.find(&0), Some(&0));
+    assert!(set.make_set(2));
+    assert_eq!(set.find(&2), Some(&2));
+}
+
+#[test]
+fn nested_checkpoints() {
+    let mut set = singletons(8);
+    let outer = set.checkpoint();
+    set.union(0, 1);
+    set.union(2, 3);
+    let middle_roots = roots(&set, 8);
+
+    let middle = set.checkpoint();
+    set.union(1, 3);
+    let inner_roots = roots(&set, 8);
+
+    let inner = set.checkpoint();
+    set.union(4, 5);
+    set.union(5, 0);
+    set.rollback(inner);
+    assert_eq!(roots(&set, 8), inner_roots);
+
+    set.union(6, 7);
+    set.rollback(middle);
+    assert_eq!(roots(&set, 8), middle_roots);
+
+    set.rollback(outer);
+    assert_eq!(roots(&set, 8), (0..8).collect::<Vec<_>>());
+    assert_eq!(set.num_sets(), 8);
+}
+
+#[test]
+fn rollback_to_current_state_is_a_no_op() {
+    let mut set = singletons(2);
+    set.union(0, 1);
+    let checkpoint = set.checkpoint();
+    set.rollback(checkpoint);
+    set.rollback(checkpoint);
+    assert_eq!(set.same_set(&0, &1), Some(true));
+}
+
+#[test]
+#[should_panic(expected = "invalidated")]
+fn rollback_to_discarded_checkpoint_panics() {
+    let mut set = singletons(3);
+    let outer = set.checkpoint();
+    set.union(0, 1);
+    let inner = set.checkpoint();
+    set.rollback(outer);
+    // The history grows back past the discarded checkpoint, which must still be rejected.
+    set.union(1, 2);
+    set.rollback(inner);
+}
+
+#[test]
+fn rollback_to_same_checkpoint_twice() {
+    let mut set = singletons(3);
+    let outer = set.checkpoint();
+    set.union(0, 1);
+    set.rollback(outer);
+    set.union(1, 2);
+    set.rollback(outer);
+    assert_eq!(set.same_set(&1, &2), Some(false));
+    assert_eq!(set.num_sets(), 3);
+}
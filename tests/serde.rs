@@ -0,0 +1,159 @@
+#![cfg(feature = "serde")]
+
+use disjoint_set::{DisjointSet, Merge};
+
+fn sorted(set: &DisjointSet<u32>) -> Vec<Vec<u32>> {
+    let mut sets: Vec<Vec<u32>> = set.sets().map(|set| {
+        let mut set: Vec<u32> = set.into_iter().cloned().collect();
+        set.sort();
+        set
+    }).collect();
+    sets.sort();
+    sets
+}
+
+#[test]
+fn round_trip_keeps_partition_and_roots() {
+    let mut set = DisjointSet::new();
+    for i in 0..10 {
+        set.make_set(i);
+    }
+    set.union(0, 1);
+    set.union(2, 3);
+    set.union(1, 3);
+    set.union(7, 8);
+    set.remove(&2);
+
+    let json = serde_json::to_string(&set).unwrap();
+    let copy: DisjointSet<u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(sorted(&copy), sorted(&set));
+    assert_eq!(copy.num_sets(), set.num_sets());
+    for i in 0..10 {
+        assert_eq!(copy.find(&i), set.find(&i));
+    }
+}
+
+#[test]
+fn round_trip_after_removing_from_highest_rank_set() {
+    let mut set = DisjointSet::new();
+    set.make_set(0);
+    set.make_set(1);
+    set.union(0, 1);
+    set.remove(&1);
+
+    let copy: DisjointSet<u32> = serde_json::from_str(&serde_json::to_string(&set).unwrap()).unwrap();
+    assert_eq!(sorted(&copy), vec![vec![0]]);
+}
+
+#[test]
+fn round_trip_keeps_ranks() {
+    let mut set = DisjointSet::new();
+    for i in 0..4 {
+        set.make_set(i);
+    }
+    set.union(0, 1);
+
+    let mut copy: DisjointSet<u32> = serde_json::from_str(&serde_json::to_string(&set).unwrap()).unwrap();
+    // {0, 1} has rank 1, so it absorbs {2} whichever side it is on.
+    assert_eq!(copy.union(2, 1).unwrap().root(), &0);
+}
+
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Count(u32);
+
+impl Merge for Count {
+    fn merge(&mut self, absorbed: Count) {
+        self.0 += absorbed.0;
+    }
+}
+
+#[test]
+fn round_trip_keeps_set_data() {
+    let mut set: DisjointSet<&str, Count> = DisjointSet::default();
+    set.make_set_with("a", Count(1));
+    set.make_set_with("b", Count(2));
+    set.make_set_with("c", Count(4));
+    set.union("a", "b");
+
+    let json = serde_json::to_string(&set).unwrap();
+    let copy: DisjointSet<String, Count> = serde_json::from_str(&json).unwrap();
+    assert_eq!(copy.set_data("b"), Some(&Count(3)));
+    assert_eq!(copy.set_data("c"), Some(&Count(4)));
+}
+
+#[test]
+fn deserialize_flattens_deep_forest() {
+    let json = r#"{
+        "nodes": [
+            {"value": 0, "parent": 1, "rank": 0},
+            {"value": 1, "parent": 2, "rank": 1},
+            {"value": 2, "parent": 2, "rank": 2},
+            {"value": 3, "parent": 3, "rank": 0}
+        ],
+        "sets": [{"root": 2, "data": null}, {"root": 3, "data": null}]
+    }"#;
+    let set: DisjointSet<u32> = serde_json::from_str(json).unwrap();
+    assert_eq!(set.find(&0), Some(&2));
+    assert_eq!(set.find(&3), Some(&3));
+    assert_eq!(sorted(&set), vec![vec![0, 1, 2], vec![3]]);
+}
+
+#[test]
+fn deserialize_rejects_cycle() {
+    let json = r#"{
+        "nodes": [
+            {"value": 0, "parent": 1, "rank": 0},
+            {"value": 1, "parent": 0, "rank": 0}
+        ],
+        "sets": []
+    }"#;
+    let error = serde_json::from_str::<DisjointSet<u32>>(json).err().unwrap();
+    assert!(error.to_string().contains("cycle"));
+}
+
+#[test]
+fn deserialize_rejects_oversized_rank() {
+    let json = r#"{
+        "nodes": [
+            {"value": 1, "parent": 1, "rank": 0},
+            {"value": 2, "parent": 1, "rank": 4294967295}
+        ],
+        "sets": [{"root": 1, "data": null}]
+    }"#;
+    let error = serde_json::from_str::<DisjointSet<u32>>(json).err().unwrap();
+    assert!(error.to_string().contains("rank"));
+}
+
+#[test]
+fn deserialize_rejects_dangling_parent() {
+    let json = r#"{"nodes": [{"value": 0, "parent": 5, "rank": 0}], "sets": [{"root": 0, "data": null}]}"#;
+    let error = serde_json::from_str::<DisjointSet<u32>>(json).err().unwrap();
+    assert!(error.to_string().contains("not in the set"));
+}
+
+#[test]
+fn deserialize_rejects_duplicate_value() {
+    let json = r#"{
+        "nodes": [
+            {"value": 0, "parent": 0, "rank": 0},
+            {"value": 0, "parent": 0, "rank": 0}
+        ],
+        "sets": [{"root": 0, "data": null}]
+    }"#;
+    assert!(serde_json::from_str::<DisjointSet<u32>>(json).is_err());
+}
+
+#[test]
+fn deserialize_rejects_missing_or_misplaced_data() {
+    let missing = r#"{"nodes": [{"value": 0, "parent": 0, "rank": 0}], "sets": []}"#;
+    assert!(serde_json::from_str::<DisjointSet<u32>>(missing).is_err());
+
+    let misplaced = r#"{
+        "nodes": [
+            {"value": 0, "parent": 0, "rank": 1},
+            {"value": 1, "parent": 0, "rank": 0}
+        ],
+        "sets": [{"root": 0, "data": null}, {"root": 1, "data": null}]
+    }"#;
+    assert!(serde_json::from_str::<DisjointSet<u32>>(misplaced).is_err());
+}
@@ -0,0 +1,181 @@
+use std::io::{self, ErrorKind};
+
+use disjoint_set::{ByMinKey, DenseDisjointSet, DenseSnapshot, DisjointSet, PathHalving};
+
+mod common;
+
+use common::random_edges;
+
+/// Builds a `DenseDisjointSet` of `n` elements with some random unions and finds, so that paths have been compressed.
+fn scrambled(n: usize) -> DenseDisjointSet {
+    let mut set = DenseDisjointSet::new(n);
+    for (a, b) in random_edges(n, n / 2, 0x2545_f491_4f6c_dd1d) {
+        set.union(a, b);
+    }
+    set.find(n / 3);
+    set
+}
+
+fn snapshot(set: &DenseDisjointSet) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    set.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+fn read_dense(bytes: &[u8]) -> io::Result<DenseDisjointSet> {
+    DenseDisjointSet::read_from(bytes)
+}
+
+/// Recomputes the trailing checksum after a snapshot has been edited.
+fn reseal(bytes: &mut [u8]) {
+    let end = bytes.len() - 8;
+    let hash = bytes[..end].iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    bytes[end..].copy_from_slice(&hash.to_le_bytes());
+}
+
+#[test]
+fn dense_round_trip_keeps_partition() {
+    let mut set = scrambled(1000);
+    let mut copy = read_dense(&snapshot(&set)).unwrap();
+    assert_eq!(copy.len(), 1000);
+    assert_eq!(copy.num_sets(), set.num_sets());
+    for element in 0..1000 {
+        assert_eq!(copy.find(element), set.find(element));
+        assert_eq!(copy.set_size(element), set.set_size(element));
+    }
+    assert_eq!(copy.union(0, 999), set.union(0, 999));
+}
+
+#[test]
+fn dense_snapshot_layout_is_fixed_width() {
+    let bytes = snapshot(&DenseDisjointSet::new(10));
+    // Header, 32-bit parents, 32-bit ranks and checksum.
+    assert_eq!(bytes.len(), 24 + 10 * 4 + 10 * 4 + 8);
+    assert_eq!(&bytes[..4], b"DSET");
+}
+
+#[test]
+fn view_answers_like_the_set() {
+    let mut set = scrambled(500);
+    let bytes = snapshot(&set);
+    let view = DenseSnapshot::from_bytes(&bytes).unwrap();
+    assert_eq!(view.len(), 500);
+    assert_eq!(view.num_sets(), set.num_sets());
+    for element in 0..500 {
+        assert_eq!(view.find(element), set.find(element));
+    }
+    assert_eq!(view.find(500), None);
+    assert_eq!(view.same_set(3, 3), Some(true));
+}
+
+#[test]
+fn corrupted_snapshot_is_rejected() {
+    let mut bytes = snapshot(&scrambled(100));
+    bytes[40] ^= 1;
+    let error = read_dense(&bytes[..]).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(DenseSnapshot::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn truncated_snapshot_is_rejected() {
+    let bytes = snapshot(&scrambled(100));
+    let error = read_dense(&bytes[..bytes.len() - 1]).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(DenseSnapshot::from_bytes(&bytes[..50]).err().unwrap().kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn cycle_is_rejected_even_with_valid_checksum() {
+    let mut set = DenseDisjointSet::new(2);
+    set.union(0, 1);
+    let mut bytes = snapshot(&set);
+    // Point element 0 at element 1, which already points at 0.
+    bytes[24..28].copy_from_slice(&1u32.to_le_bytes());
+    reseal(&mut bytes);
+    assert_eq!(read_dense(&bytes[..]).err().unwrap().kind(), ErrorKind::InvalidData);
+    assert!(DenseSnapshot::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn oversized_rank_is_rejected_even_with_valid_checksum() {
+    let mut bytes = snapshot(&DenseDisjointSet::new(2));
+    // Give both roots a rank that would overflow on the next union.
+    bytes[32..40].copy_from_slice(&[0xff; 8]);
+    reseal(&mut bytes);
+    assert_eq!(read_dense(&bytes[..]).err().unwrap().kind(), ErrorKind::InvalidData);
+    assert!(DenseSnapshot::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn dangling_parent_is_rejected_even_with_valid_checksum() {
+    let mut bytes = snapshot(&DenseDisjointSet::new(2));
+    bytes[24..28].copy_from_slice(&7u32.to_le_bytes());
+    reseal(&mut bytes);
+    assert_eq!(read_dense(&bytes[..]).err().unwrap().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn keyed_round_trip_keeps_values_roots_and_data() {
+    let mut set: DisjointSet<String, u64> = DisjointSet::default();
+    for i in 0..20u64 {
+        set.make_set_with(format!("v{}", i), i);
+    }
+    for i in 0..10u64 {
+        set.union_with(format!("v{}", i), format!("v{}", i + 5), |root, absorbed| *root += absorbed);
+    }
+    set.remove("v3");
+    set.remove("v12");
+
+    let mut bytes = Vec::new();
+    set.write_to(&mut bytes).unwrap();
+    let copy: DisjointSet<String, u64> = DisjointSet::read_from(&bytes[..]).unwrap();
+    assert_eq!(copy.len(), set.len());
+    assert_eq!(copy.num_sets(), set.num_sets());
+    for i in 0..20 {
+        let value = format!("v{}", i);
+        assert_eq!(copy.find(&value), set.find(&value));
+        assert_eq!(copy.set_data(&value), set.set_data(&value));
+    }
+}
+
+#[test]
+fn keyed_round_trip_after_removals_from_tall_trees() {
+    let mut set = DisjointSet::new();
+    set.make_set(0u32);
+    set.make_set(1);
+    set.union(0, 1);
+    set.remove(&1);
+    let mut bytes = Vec::new();
+    set.write_to(&mut bytes).unwrap();
+    let copy = DisjointSet::<u32>::read_from(&bytes[..]).unwrap();
+    assert_eq!(copy.find(&0), Some(&0));
+
+    // Linking by key builds a chain whose rank grows with every union.
+    let mut chain = DisjointSet::with_linking(ByMinKey);
+    for i in 0..6u32 {
+        chain.make_set(i);
+    }
+    for i in (0..5).rev() {
+        chain.union(i, i + 1);
+    }
+    for i in [1, 3, 5].iter() {
+        chain.remove(i);
+    }
+    let mut bytes = Vec::new();
+    chain.write_to(&mut bytes).unwrap();
+    let copy = DisjointSet::<u32, (), PathHalving, ByMinKey>::read_from(&bytes[..]).unwrap();
+    assert_eq!(copy.len(), 3);
+    for i in [0, 2, 4].iter() {
+        assert_eq!(copy.find(i), Some(&0));
+    }
+}
+
+#[test]
+fn snapshot_kinds_are_not_interchangeable() {
+    let bytes = snapshot(&DenseDisjointSet::new(3));
+    let error = DisjointSet::<u32>::read_from(&bytes[..]).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,97 @@
+use disjoint_set::{DisjointSet, TimedDisjointSet, UnionResult};
+
+mod common;
+
+use common::XorShift;
+
+fn singletons(n: u32) -> TimedDisjointSet<u32> {
+    let mut set = TimedDisjointSet::new();
+    for i in 0..n {
+        set.make_set(i);
+    }
+    set
+}
+
+#[test]
+fn union_advances_time() {
+    let mut set = singletons(3);
+    assert_eq!(set.time(), 0);
+    assert_eq!(set.union(0, 1), Some(UnionResult::Merged { root: 0, absorbed: 1 }));
+    assert_eq!(set.union(1, 0), Some(UnionResult::AlreadyJoined(0)));
+    assert_eq!(set.time(), 2);
+    assert_eq!(set.union(0, 7), None);
+    assert_eq!(set.time(), 2);
+    assert!(!set.make_set(2));
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn connected_at_sees_history() {
+    let mut set = singletons(4);
+    set.union(0, 1); // time 1
+    set.union(2, 3); // time 2
+    set.union(1, 3); // time 3
+
+    assert_eq!(set.connected_at(&0, &1, 0), Some(false));
+    assert_eq!(set.connected_at(&0, &1, 1), Some(true));
+    assert_eq!(set.connected_at(&0, &3, 2), Some(false));
+    assert_eq!(set.connected_at(&0, &3, 3), Some(true));
+    assert_eq!(set.connected_at(&0, &3, usize::MAX), Some(true));
+    assert_eq!(set.connected_at(&0, &9, 3), None);
+    assert_eq!(set.same_set(&1, &2), Some(true));
+
+    assert_eq!(set.find_at(&3, 1), Some(&3));
+    assert_eq!(set.find_at(&3, 2), Some(&2));
+    assert_eq!(set.find(&3), Some(&0));
+}
+
+#[test]
+fn first_connected_reports_union_time() {
+    let mut set = singletons(5);
+    set.union(0, 1); // time 1
+    set.union(2, 3); // time 2
+    set.union(0, 1); // time 3, already joined
+    set.union(3, 0); // time 4
+
+    assert_eq!(set.first_connected(&2, &2), Some(0));
+    assert_eq!(set.first_connected(&1, &0), Some(1));
+    assert_eq!(set.first_connected(&2, &3), Some(2));
+    assert_eq!(set.first_connected(&1, &3), Some(4));
+    assert_eq!(set.first_connected(&1, &4), None);
+    assert_eq!(set.first_connected(&1, &9), None);
+}
+
+#[test]
+fn matches_snapshots_of_sequential_history() {
+    const N: u32 = 200;
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    let mut next = move || rng.next(N as usize) as u32;
+
+    let mut timed = singletons(N);
+    let mut sequential = DisjointSet::new();
+    for i in 0..N {
+        sequential.make_set(i);
+    }
+    let mut snapshots = vec![sequential.clone()];
+    for _ in 0..150 {
+        let (a, b) = (next(), next());
+        timed.union(a, b);
+        sequential.union(a, b);
+        snapshots.push(sequential.clone());
+    }
+
+    for (time, snapshot) in snapshots.iter().enumerate() {
+        for a in (0..N).step_by(7) {
+            for b in (0..N).step_by(5) {
+                assert_eq!(timed.connected_at(&a, &b, time), Some(snapshot.find(&a) == snapshot.find(&b)));
+            }
+        }
+    }
+
+    for a in (0..N).step_by(3) {
+        for b in (0..N).step_by(11) {
+            let expected = snapshots.iter().position(|snapshot| snapshot.find(&a) == snapshot.find(&b));
+            assert_eq!(timed.first_connected(&a, &b), expected);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use disjoint_set::{Group, UnionResult, WeightedDisjointSet, WeightedUnionError};
+
+mod common;
+
+use common::XorShift;
+
+fn singletons(n: u32) -> WeightedDisjointSet<u32, i64> {
+    let mut set = WeightedDisjointSet::new();
+    for i in 0..n {
+        set.make_set(i);
+    }
+    set
+}
+
+#[test]
+fn integer_group_laws() {
+    assert_eq!(i32::identity(), 0);
+    assert_eq!(5i32.combine(&-7), -2);
+    assert_eq!(5i32.inverse(), -5);
+    assert_eq!(i8::MIN.inverse(), i8::MIN);
+    assert_eq!(3u8.combine(&3u8.inverse()), 0);
+}
+
+#[test]
+fn diff_follows_recorded_offsets() {
+    let mut set = singletons(4);
+    assert_eq!(set.union(0, 1, 5), Ok(UnionResult::Merged { root: 0, absorbed: 1 }));
+    set.union(2, 1, 3).unwrap();
+    set.union(3, 2, -10).unwrap();
+
+    // value(1) = value(0) + 5, value(1) = value(2) + 3, value(2) = value(3) - 10
+    assert_eq!(set.diff(&0, &1), Some(5));
+    assert_eq!(set.diff(&1, &0), Some(-5));
+    assert_eq!(set.diff(&0, &2), Some(2));
+    assert_eq!(set.diff(&0, &3), Some(12));
+    assert_eq!(set.diff(&3, &3), Some(0));
+    assert_eq!(set.num_sets(), 1);
+}
+
+#[test]
+fn diff_of_unrelated_or_missing_is_none() {
+    let mut set = singletons(3);
+    set.union(0, 1, 1).unwrap();
+    assert_eq!(set.diff(&0, &2), None);
+    assert_eq!(set.diff(&0, &9), None);
+}
+
+#[test]
+fn consistent_union_in_same_set_is_accepted() {
+    let mut set = singletons(3);
+    set.union(0, 1, 4).unwrap();
+    set.union(1, 2, 6).unwrap();
+    assert_eq!(set.union(0, 2, 10), Ok(UnionResult::AlreadyJoined(0)));
+    assert_eq!(set.union(2, 0, -10), Ok(UnionResult::AlreadyJoined(0)));
+}
+
+#[test]
+fn inconsistent_union_is_rejected() {
+    let mut set = singletons(3);
+    set.union(0, 1, 4).unwrap();
+    set.union(1, 2, 6).unwrap();
+    assert_eq!(set.union(0, 2, 11), Err(WeightedUnionError::Inconsistent { existing: 10 }));
+    assert_eq!(set.diff(&0, &2), Some(10));
+}
+
+#[test]
+fn missing_value_is_rejected() {
+    let mut set = singletons(1);
+    assert_eq!(set.union(0, 1, 0), Err(WeightedUnionError::MissingValue));
+    assert_eq!(set.union(1, 0, 0), Err(WeightedUnionError::MissingValue));
+}
+
+#[test]
+fn path_compression_keeps_offsets() {
+    const N: u32 = 300;
+    let mut set = singletons(N);
+    // Build a long chain with value(i) = i * i, joining in an order that creates deep trees.
+    for i in (1..N).rev() {
+        set.union(i - 1, i, (i * i) as i64 - ((i - 1) * (i - 1)) as i64).unwrap();
+    }
+    for step in 1..5 {
+        for i in (0..N).step_by(step) {
+            let root = *set.find_mut(&i).unwrap();
+            assert_eq!(set.find(&i), Some(&root));
+            assert_eq!(set.diff(&0, &i), Some((i * i) as i64));
+            assert_eq!(set.diff(&i, &(N - 1)), Some(((N - 1) * (N - 1)) as i64 - (i * i) as i64));
+        }
+    }
+}
+
+#[test]
+fn matches_brute_force_potentials() {
+    const N: u32 = 200;
+    let mut rng = XorShift(0x1234_5678_9ABC_DEF1);
+    let mut next = move |bound: u64| rng.next(bound as usize) as u64;
+
+    let potentials: Vec<i64> = (0..N).map(|_| next(1000) as i64 - 500).collect();
+    let mut set = singletons(N);
+    for _ in 0..400 {
+        let (a, b) = (next(N as u64) as u32, next(N as u64) as u32);
+        let weight = potentials[b as usize] - potentials[a as usize];
+        assert!(set.union(a, b, weight).is_ok());
+        if set.diff(&a, &b).is_some() {
+            assert!(set.union(a, b, weight + 1).is_err());
+        }
+    }
+    for a in 0..N {
+        for b in (0..N).step_by(13) {
+            if let Some(diff) = set.diff(&a, &b) {
+                assert_eq!(diff, potentials[b as usize] - potentials[a as usize]);
+            }
+        }
+    }
+}
+
+#[test]
+fn same_set_and_len() {
+    let mut set = singletons(3);
+    assert!(!set.make_set(2));
+    assert_eq!(set.len(), 3);
+    set.union(0, 2, 1).unwrap();
+    assert_eq!(set.same_set(&2, &0), Some(true));
+    assert_eq!(set.same_set(&1, &0), Some(false));
+    assert_eq!(set.same_set(&1, &5), None);
+}